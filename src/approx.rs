@@ -0,0 +1,86 @@
+//! Approximate comparisons of floating-point numbers.
+use std::fmt;
+
+/// The tolerance of an approximate comparison.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tolerance<F> {
+    /// The numbers differ by at most this value.
+    Absolute(F),
+    /// The numbers differ by at most this fraction of the largest one, in absolute value.
+    Relative(F),
+    /// At most this number of representable floating-point numbers lie between the numbers.
+    Ulps(u64),
+}
+
+impl<F: fmt::Debug> fmt::Display for Tolerance<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Tolerance::Absolute(epsilon) => write!(f, "{:?}", epsilon),
+            Tolerance::Relative(epsilon) => write!(f, "relative {:?}", epsilon),
+            Tolerance::Ulps(ulps) => write!(f, "ulps {}", ulps),
+        }
+    }
+}
+
+/// The floating-point numbers that can be compared approximately.
+pub trait Float: Copy + PartialEq + fmt::Debug {
+    fn to_f64(self) -> f64;
+
+    /// The number of representable numbers between `self` and `other`.
+    fn ulps_from(self, other: Self) -> u64;
+}
+
+impl Float for f32 {
+    fn to_f64(self) -> f64 {
+        f64::from(self)
+    }
+
+    fn ulps_from(self, other: f32) -> u64 {
+        if self.is_nan() || other.is_nan() {
+            return u64::MAX;
+        }
+        // maps the bits to integers ordered like the numbers, with both zeros at 0
+        let ordered = |x: f32| {
+            let bits = i64::from(x.to_bits() as i32);
+            if bits < 0 { i64::from(i32::MIN) - bits } else { bits }
+        };
+        (ordered(self) - ordered(other)).unsigned_abs()
+    }
+}
+
+impl Float for f64 {
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn ulps_from(self, other: f64) -> u64 {
+        if self.is_nan() || other.is_nan() {
+            return u64::MAX;
+        }
+        let ordered = |x: f64| {
+            let bits = i128::from(x.to_bits() as i64);
+            if bits < 0 { i128::from(i64::MIN) - bits } else { bits }
+        };
+        let ulps = (ordered(self) - ordered(other)).unsigned_abs();
+        if ulps > u128::from(u64::MAX) { u64::MAX } else { ulps as u64 }
+    }
+}
+
+/// Whether `actual` is close to `expected`, and the difference between them, as shown in failure messages.
+pub fn close<F: Float>(actual: F, expected: F, tolerance: Tolerance<F>) -> (bool, String) {
+    match tolerance {
+        Tolerance::Absolute(epsilon) => {
+            let difference = (actual.to_f64() - expected.to_f64()).abs();
+            (actual == expected || difference <= epsilon.to_f64(), format!("difference = {:?}", difference))
+        }
+        Tolerance::Relative(epsilon) => {
+            let (actual, expected) = (actual.to_f64(), expected.to_f64());
+            let difference = (actual - expected).abs() / actual.abs().max(expected.abs());
+            (actual == expected || difference <= epsilon.to_f64(), format!("relative difference = {:?}", difference))
+        }
+        Tolerance::Ulps(ulps) => {
+            let difference = actual.ulps_from(expected);
+            (difference <= ulps, format!("difference = {} ulps", difference))
+        }
+    }
+}
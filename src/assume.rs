@@ -0,0 +1,16 @@
+//! Early returns of the tests whose assumptions do not hold.
+
+/// The value returned by a function skipped by `assume_that!`.
+pub trait Skip {
+    fn skipped() -> Self;
+}
+
+impl Skip for () {
+    fn skipped() {}
+}
+
+impl<E> Skip for Result<(), E> {
+    fn skipped() -> Self {
+        Ok(())
+    }
+}
@@ -0,0 +1,176 @@
+//! `cargo doubts`: reviews the pending snapshots written by failing `matches snapshot` checks.
+//!
+//! Each pending `.snap.new` file found under the given directory, or the current one, is shown as a diff
+//! with its snapshot, then accepted (replacing the snapshot), rejected (deleted) or skipped.
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+
+use doubts::diff;
+use doubts::snapshots::PENDING;
+
+#[cfg(test)]
+#[path = "../temporary.rs"]
+mod temporary;
+
+const USAGE: &str = "Reviews the pending snapshots written by failing `matches snapshot` checks.
+
+Usage: cargo doubts [--accept-all | --reject-all] [DIRECTORY]
+
+Options:
+    --accept-all    Accept every pending snapshot, without asking
+    --reject-all    Reject every pending snapshot, without asking
+    -h, --help      Print this help";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Decision {
+    Accept,
+    Reject,
+    Skip,
+}
+
+fn main() {
+    let mut batch = None;
+    let mut root = PathBuf::from(".");
+    // cargo passes the name of the subcommand as the first argument
+    for argument in env::args().skip(1).skip_while(|argument| argument == "doubts") {
+        match argument.as_str() {
+            "--accept-all" => batch = Some(Decision::Accept),
+            "--reject-all" => batch = Some(Decision::Reject),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            option if option.starts_with('-') => fail(&format!("unknown option {}\n\n{}", option, USAGE)),
+            directory => root = PathBuf::from(directory),
+        }
+    }
+    let mut pending = Vec::new();
+    if let Err(error) = find(&root, &mut pending) {
+        fail(&format!("cannot search {}: {}", root.display(), error));
+    }
+    pending.sort();
+    if pending.is_empty() {
+        println!("No pending snapshot.");
+        return;
+    }
+    let mut counts = [0; 3];
+    for path in &pending {
+        let decision = match batch {
+            Some(decision) => decision,
+            None => review(path).unwrap_or_else(|error| fail(&format!("cannot review {}: {}", path.display(), error))),
+        };
+        if let Err(error) = apply(path, decision) {
+            fail(&format!("cannot update {}: {}", path.display(), error));
+        }
+        counts[decision as usize] += 1;
+    }
+    println!("{} accepted, {} rejected, {} skipped.", counts[0], counts[1], counts[2]);
+}
+
+/// Collects the pending snapshots under `directory`, leaving out the hidden and `target` directories.
+fn find(directory: &Path, pending: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        let name = path.file_name().map_or_else(String::new, |name| name.to_string_lossy().into_owned());
+        if path.is_dir() {
+            if !name.starts_with('.') && name != "target" {
+                find(&path, pending)?;
+            }
+        } else if name.ends_with(&format!(".{}", PENDING)) {
+            pending.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Shows the diff between the snapshot and its pending version, and asks what to do with it.
+fn review(path: &Path) -> io::Result<Decision> {
+    let snapshot = snapshot(path);
+    let expected = fs::read_to_string(&snapshot).unwrap_or_default();
+    let actual = fs::read_to_string(path)?;
+    println!("{}", snapshot.display());
+    println!("Diff (- snapshot / + pending):\n{}", diff::colored(&expected, &actual).trim_end());
+    let stdin = io::stdin();
+    loop {
+        print!("[a]ccept, [r]eject or [s]kip? ");
+        io::stdout().flush()?;
+        let mut answer = String::new();
+        if stdin.lock().read_line(&mut answer)? == 0 {
+            return Ok(Decision::Skip);
+        }
+        match answer.trim() {
+            "a" | "accept" => return Ok(Decision::Accept),
+            "r" | "reject" => return Ok(Decision::Reject),
+            "s" | "skip" | "" => return Ok(Decision::Skip),
+            _ => continue,
+        }
+    }
+}
+
+fn apply(path: &Path, decision: Decision) -> io::Result<()> {
+    match decision {
+        Decision::Accept => fs::rename(path, snapshot(path)),
+        Decision::Reject => fs::remove_file(path),
+        Decision::Skip => Ok(()),
+    }
+}
+
+/// The snapshot of a pending snapshot, `api__name.snap` for `api__name.snap.new`.
+fn snapshot(pending: &Path) -> PathBuf {
+    pending.with_extension("")
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("error: {}", message);
+    process::exit(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::temporary::Directory;
+
+    #[test]
+    fn find_leaves_out_hidden_and_target_directories() {
+        let directory = Directory::new("find", &[
+            "tests/snapshots/api__a.snap",
+            "tests/snapshots/api__a.snap.new",
+            "crates/core/tests/snapshots/api__b.snap.new",
+            "target/tests/snapshots/api__c.snap.new",
+            ".git/api__d.snap.new",
+        ]);
+        let mut pending = Vec::new();
+        find(&directory.0, &mut pending).unwrap();
+        pending.sort();
+        assert_eq!(pending, vec![
+            directory.0.join("crates/core/tests/snapshots/api__b.snap.new"),
+            directory.0.join("tests/snapshots/api__a.snap.new"),
+        ]);
+    }
+
+    #[test]
+    fn snapshot_drops_the_pending_extension() {
+        assert_eq!(snapshot(Path::new("tests/snapshots/api__name.snap.new")), PathBuf::from("tests/snapshots/api__name.snap"));
+    }
+
+    #[test]
+    fn apply_replaces_deletes_or_keeps_the_pending_snapshot() {
+        let directory = Directory::new("apply", &["accept.snap", "accept.snap.new", "reject.snap", "reject.snap.new", "skip.snap.new"]);
+        let path = |file: &str| directory.0.join(file);
+
+        apply(&path("accept.snap.new"), Decision::Accept).unwrap();
+        assert!(!path("accept.snap.new").exists());
+        assert_eq!(fs::read_to_string(path("accept.snap")).unwrap(), "accept.snap.new");
+
+        apply(&path("reject.snap.new"), Decision::Reject).unwrap();
+        assert!(!path("reject.snap.new").exists());
+        assert_eq!(fs::read_to_string(path("reject.snap")).unwrap(), "reject.snap");
+
+        apply(&path("skip.snap.new"), Decision::Skip).unwrap();
+        assert!(path("skip.snap.new").exists());
+        assert!(!path("skip.snap").exists());
+    }
+}
@@ -0,0 +1,274 @@
+//! Checks on the elements of collections.
+use std::cmp::Ordering;
+use std::fmt::Debug;
+
+use crate::render;
+use crate::verdict::Verdict;
+use crate::Failure;
+
+/// How many elements of a collection should meet a predicate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Quantifier {
+    Any,
+    None,
+}
+
+/// Gathers the failures of the elements of a collection, by index, into a single one.
+pub fn each<C: Debug + ?Sized>(subject: &str, actual: &C, failures: Vec<(usize, Box<Failure>)>) -> Result<(), Box<Failure>> {
+    let expectation = match failures.first() {
+        None => return Ok(()),
+        Some((_, failure)) => failure.expectation(),
+    };
+    let offending: Vec<String> = failures.iter()
+        .map(|(i, failure)| match failure.explanation() {
+            Some(explanation) => format!("[{}] = {} ({})", i, failure.subject_debug(), explanation),
+            None => format!("[{}] = {}", i, failure.subject_debug()),
+        })
+        .collect();
+    Err(Box::new(Failure::new(subject, render::debug(actual), format!("have each element {}", expectation))
+        .with_reason(format!("it fails for {}", offending.join(", ")))))
+}
+
+/// Checks that the elements meeting `predicate` are as many as the quantifier asks, listing the offending ones by index.
+pub fn quantified<C, I, X>(quantifier: Quantifier, subject: &str, actual: &C, elements: I, description: String, predicate: impl Fn(&X) -> bool) -> Result<(), Box<Failure>>
+    where C: Debug + ?Sized, I: IntoIterator<Item = X>, X: Debug {
+    let meeting: Vec<_> = elements.into_iter().enumerate().filter(|(_, element)| predicate(element)).collect();
+    let (verb, offending, reason) = match quantifier {
+        Quantifier::None if meeting.is_empty() => return Ok(()),
+        Quantifier::None => ("have no element", meeting, "it holds for"),
+        Quantifier::Any if !meeting.is_empty() => return Ok(()),
+        Quantifier::Any => ("have some element", Vec::new(), ""),
+    };
+    let failure = Failure::new(subject, render::debug(actual), format!("{} {}", verb, description));
+    Err(Box::new(if offending.is_empty() {
+        failure
+    } else {
+        failure.with_reason(format!("{} {}", reason, indexed(&offending)))
+    }))
+}
+
+/// Checks that every expected element is in the collection.
+pub fn contains_all<C, A, B>(subject: &str, actual: &C, elements: impl IntoIterator<Item = A>, expected: impl IntoIterator<Item = B>) -> Result<(), Box<Failure>>
+    where C: Debug + ?Sized, A: PartialEq<B> + Debug, B: Debug {
+    let elements: Vec<A> = elements.into_iter().collect();
+    let expected: Vec<B> = expected.into_iter().collect();
+    let missing: Vec<&B> = expected.iter().filter(|e| !elements.iter().any(|element| element == *e)).collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+    Err(Box::new(Failure::new(subject, render::debug(actual), "contain all of")
+        .with_expected(render::debug(&expected))
+        .with_reason(format!("missing {}", listed(&missing)))))
+}
+
+/// Checks that the collection has exactly the expected elements, in the same order or in any order.
+pub fn contains_exactly<C, A, B>(subject: &str, actual: &C, elements: impl IntoIterator<Item = A>, expected: impl IntoIterator<Item = B>, in_any_order: bool) -> Result<(), Box<Failure>>
+    where C: Debug + ?Sized, A: PartialEq<B> + Debug, B: Debug {
+    let elements: Vec<A> = elements.into_iter().collect();
+    let expected: Vec<B> = expected.into_iter().collect();
+    let mut differences = Vec::new();
+    if in_any_order {
+        let (missing, unexpected) = unmatched(&elements, &expected);
+        if !missing.is_empty() {
+            differences.push(format!("missing {}", listed(&missing.iter().map(|&i| &expected[i]).collect::<Vec<_>>())));
+        }
+        if !unexpected.is_empty() {
+            differences.push(format!("unexpected {}", indexed(&unexpected.iter().map(|&i| (i, &elements[i])).collect::<Vec<_>>())));
+        }
+    } else {
+        let common = elements.len().min(expected.len());
+        for i in (0..common).filter(|&i| elements[i] != expected[i]) {
+            differences.push(format!("[{}] = {} instead of {}", i, render::debug(&elements[i]), render::debug(&expected[i])));
+        }
+        if expected.len() > common {
+            differences.push(format!("missing {}", listed(&expected[common..].iter().collect::<Vec<_>>())));
+        }
+        if elements.len() > common {
+            differences.push(format!("unexpected {}", indexed(&elements.iter().enumerate().skip(common).collect::<Vec<_>>())));
+        }
+    }
+    if differences.is_empty() {
+        return Ok(());
+    }
+    Err(Box::new(Failure::new(subject, render::debug(actual), "contain exactly")
+        .with_expected(format!("{}{}", render::debug(&expected), if in_any_order { " in any order" } else { "" }))
+        .with_reason(differences.join("; "))))
+}
+
+/// Checks that the collection has the same elements as the expected ones, in any order and with as many duplicates,
+/// or not when `negated`.
+pub fn same_elements<C, A, B>(subject: &str, actual: &C, elements: impl IntoIterator<Item = A>, expected: impl IntoIterator<Item = B>, negated: bool) -> Result<(), Box<Failure>>
+    where C: Debug + ?Sized, A: PartialEq<B> + Debug, B: Debug {
+    let elements: Vec<A> = elements.into_iter().collect();
+    let expected: Vec<B> = expected.into_iter().collect();
+    let (missing, extra) = unmatched(&elements, &expected);
+    let failure = Failure::new(subject, render::debug(actual), "have the same elements as").with_expected(render::debug(&expected));
+    match (negated, missing.is_empty() && extra.is_empty()) {
+        (false, true) | (true, false) => Ok(()),
+        (true, true) => Err(Box::new(failure.negated())),
+        (false, false) => {
+            let mut differences = Vec::new();
+            if !missing.is_empty() {
+                differences.push(format!("missing {}", counted(missing.iter().map(|&i| &expected[i]))));
+            }
+            if !extra.is_empty() {
+                differences.push(format!("extra {}", counted(extra.iter().map(|&i| &elements[i]))));
+            }
+            Err(Box::new(failure.with_reason(differences.join("; "))))
+        }
+    }
+}
+
+/// Checks that the keys of consecutive elements are in increasing order.
+pub fn sorted_by_key<C, X, K>(subject: &str, actual: &C, elements: impl IntoIterator<Item = X>, description: &str, key: impl Fn(&X) -> K) -> Result<(), Box<Failure>>
+    where C: Debug + ?Sized, X: Debug, K: PartialOrd + Debug {
+    let elements: Vec<X> = elements.into_iter().collect();
+    let keys: Vec<K> = elements.iter().map(key).collect();
+    match (1..elements.len()).find(|&i| matches!(keys[i - 1].partial_cmp(&keys[i]), Some(Ordering::Greater) | None)) {
+        None => Ok(()),
+        Some(i) => {
+            let shown = |i: usize| if description.is_empty() {
+                format!("[{}] = {}", i, render::debug(&elements[i]))
+            } else {
+                format!("[{}] = {} (key {})", i, render::debug(&elements[i]), render::debug(&keys[i]))
+            };
+            let failure = Failure::new(subject, render::debug(actual), "be sorted")
+                .with_reason(format!("{} comes before {}", shown(i - 1), shown(i)));
+            Err(Box::new(if description.is_empty() { failure } else { failure.with_expected(format!("by key {}", description)) }))
+        }
+    }
+}
+
+/// A checked collection, whose elements are iterated by reference when it can be, or from a copy for the iterators and slices,
+/// dispatched as described on `__doubts_check!`.
+pub struct Elements<'a, C: ?Sized>(pub &'a C);
+
+/// The elements of the collections whose reference can be iterated.
+pub trait Borrowed {
+    type Iter: Iterator;
+
+    fn elements(&self) -> Self::Iter;
+}
+
+impl<'a, C: ?Sized> Borrowed for Elements<'a, C> where &'a C: IntoIterator {
+    type Iter = <&'a C as IntoIterator>::IntoIter;
+
+    fn elements(&self) -> Self::Iter {
+        self.0.into_iter()
+    }
+}
+
+/// The fallback for the iterators and slices, iterated from a copy.
+pub trait Cloned {
+    type Iter: Iterator;
+
+    fn elements(&self) -> Self::Iter;
+}
+
+impl<C: IntoIterator + Clone> Cloned for &Elements<'_, C> {
+    type Iter = C::IntoIter;
+
+    fn elements(&self) -> C::IntoIter {
+        self.0.clone().into_iter()
+    }
+}
+
+/// The `is_sorted` and `is_sorted_by_key` checks of the collections whose reference can be iterated,
+/// named like the methods so that the values having their own methods keep them.
+pub trait Sortable<'a> {
+    type Elements;
+
+    fn is_sorted(&'a self) -> Sorting<Self::Elements, ()>;
+
+    fn is_sorted_by_key<F>(&'a self, key: F) -> Sorting<Self::Elements, F>;
+}
+
+impl<'a, C: ?Sized + 'a> Sortable<'a> for C where &'a C: IntoIterator {
+    type Elements = <&'a C as IntoIterator>::IntoIter;
+
+    fn is_sorted(&'a self) -> Sorting<Self::Elements, ()> {
+        Sorting { elements: self.into_iter(), key: () }
+    }
+
+    fn is_sorted_by_key<F>(&'a self, key: F) -> Sorting<Self::Elements, F> {
+        Sorting { elements: self.into_iter(), key }
+    }
+}
+
+/// The same checks for the iterators and slices, whose elements are iterated from a copy, implemented for their reference
+/// to come after `Sortable`.
+pub trait ClonedSortable<C: IntoIterator> {
+    fn is_sorted(&self) -> Sorting<C::IntoIter, ()>;
+
+    fn is_sorted_by_key<F>(&self, key: F) -> Sorting<C::IntoIter, F>;
+}
+
+impl<C: IntoIterator + Clone> ClonedSortable<C> for &C {
+    fn is_sorted(&self) -> Sorting<C::IntoIter, ()> {
+        Sorting { elements: (*self).clone().into_iter(), key: () }
+    }
+
+    fn is_sorted_by_key<F>(&self, key: F) -> Sorting<C::IntoIter, F> {
+        Sorting { elements: (*self).clone().into_iter(), key }
+    }
+}
+
+/// The elements of a collection, to check that they are sorted, by themselves or by `key`.
+pub struct Sorting<I, F> {
+    elements: I,
+    key: F,
+}
+
+impl<C, I> Verdict<C> for Sorting<I, ()> where C: Debug + ?Sized, I: Iterator, I::Item: PartialOrd + Debug + Clone {
+    fn verdict(self, subject: &str, actual: &C, arguments: &str, _negated: bool) -> Result<(), Option<Box<Failure>>> {
+        sorted_by_key(subject, actual, self.elements, arguments, |element| element.clone()).map_err(Some)
+    }
+}
+
+impl<'a, C, I, X, F, K> Verdict<C> for Sorting<I, F>
+    where C: Debug + ?Sized, I: Iterator<Item = &'a X>, X: Debug + ?Sized + 'a, F: Fn(&X) -> K, K: PartialOrd + Debug {
+    fn verdict(self, subject: &str, actual: &C, arguments: &str, _negated: bool) -> Result<(), Option<Box<Failure>>> {
+        let key = self.key;
+        sorted_by_key(subject, actual, self.elements, arguments, |element| key(*element)).map_err(Some)
+    }
+}
+
+/// The indexes of the elements matching no expected element, and of the expected elements matching no element.
+pub(crate) fn unmatched<A: PartialEq<B>, B>(elements: &[A], expected: &[B]) -> (Vec<usize>, Vec<usize>) {
+    let mut matched = vec![false; expected.len()];
+    let mut unexpected = Vec::new();
+    for (i, element) in elements.iter().enumerate() {
+        match (0..expected.len()).find(|&j| !matched[j] && *element == expected[j]) {
+            Some(j) => matched[j] = true,
+            None => unexpected.push(i),
+        }
+    }
+    let missing = (0..expected.len()).filter(|&j| !matched[j]).collect();
+    (missing, unexpected)
+}
+
+/// The elements, with the number of times they appear when they are repeated, like `"a" (2 times), "b"`,
+/// sorted by their `Debug` representation so that the list does not depend on the order of the collection.
+fn counted<X: Debug>(elements: impl IntoIterator<Item = X>) -> String {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for element in elements {
+        let shown = render::debug(&element);
+        match counts.iter_mut().find(|(other, _)| *other == shown) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((shown, 1)),
+        }
+    }
+    counts.sort();
+    counts.into_iter()
+        .map(|(shown, count)| if count > 1 { format!("{} ({} times)", shown, count) } else { shown })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn listed<X: Debug>(elements: &[X]) -> String {
+    elements.iter().map(render::debug).collect::<Vec<_>>().join(", ")
+}
+
+fn indexed<X: Debug>(elements: &[(usize, X)]) -> String {
+    elements.iter().map(|(i, element)| format!("[{}] = {}", i, render::debug(element))).collect::<Vec<_>>().join(", ")
+}
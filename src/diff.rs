@@ -0,0 +1,71 @@
+//! Line-by-line diff between an expected and an actual text.
+
+/// A line of a diff.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Line<'a> {
+    Both(&'a str),
+    Expected(&'a str),
+    Actual(&'a str),
+}
+
+/// The lines of both texts, in order, as a longest common subsequence diff.
+pub fn lines<'a>(expected: &'a str, actual: &'a str) -> Vec<Line<'a>> {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    // common[i][j] is the length of the longest common subsequence of expected[i..] and actual[j..]
+    let mut common = vec![vec![0; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            common[i][j] = if expected[i] == actual[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut lines = Vec::new();
+    while i < expected.len() && j < actual.len() {
+        if expected[i] == actual[j] {
+            lines.push(Line::Both(expected[i]));
+            i += 1;
+            j += 1;
+        } else if common[i + 1][j] >= common[i][j + 1] {
+            lines.push(Line::Expected(expected[i]));
+            i += 1;
+        } else {
+            lines.push(Line::Actual(actual[j]));
+            j += 1;
+        }
+    }
+    lines.extend(expected[i..].iter().map(|line| Line::Expected(line)));
+    lines.extend(actual[j..].iter().map(|line| Line::Actual(line)));
+    lines
+}
+
+/// The diff, with expected lines prefixed by `-` in red and actual lines by `+` in green.
+/// Colors are left out when the `NO_COLOR` environment variable is set.
+pub fn colored(expected: &str, actual: &str) -> String {
+    if std::env::var_os("NO_COLOR").is_some() {
+        plain(expected, actual)
+    } else {
+        write(expected, actual, ("\x1b[31m", "\x1b[32m", "\x1b[0m"))
+    }
+}
+
+/// The diff, with expected lines prefixed by `-` and actual lines by `+`.
+pub fn plain(expected: &str, actual: &str) -> String {
+    write(expected, actual, ("", "", ""))
+}
+
+fn write(expected: &str, actual: &str, (red, green, reset): (&str, &str, &str)) -> String {
+    let mut diff = String::new();
+    for line in lines(expected, actual) {
+        match line {
+            Line::Both(line) => diff.push_str(&format!(" {}\n", line)),
+            Line::Expected(line) => diff.push_str(&format!("{}-{}{}\n", red, line, reset)),
+            Line::Actual(line) => diff.push_str(&format!("{}+{}{}\n", green, line, reset)),
+        }
+    }
+    diff
+}
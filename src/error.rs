@@ -0,0 +1,45 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::Failure;
+
+/// A failed expectation, as returned by [`verify_that!`](macro.verify_that.html).
+///
+/// It holds the [`Failure`](struct.Failure.html) with every piece of the message, so that harnesses can inspect them;
+/// its `Display` implementation gives the same message as a failing `assert_that!`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DoubtError {
+    // boxed, to keep `Result<_, DoubtError>` small
+    failure: Box<Failure>,
+}
+
+impl DoubtError {
+    /// The pieces of the failure message.
+    pub fn failure(&self) -> &Failure {
+        &self.failure
+    }
+
+    pub fn into_failure(self) -> Failure {
+        *self.failure
+    }
+}
+
+impl From<Failure> for DoubtError {
+    fn from(failure: Failure) -> DoubtError {
+        DoubtError { failure: Box::new(failure) }
+    }
+}
+
+impl From<Box<Failure>> for DoubtError {
+    fn from(failure: Box<Failure>) -> DoubtError {
+        DoubtError { failure }
+    }
+}
+
+impl fmt::Display for DoubtError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.failure.fmt(f)
+    }
+}
+
+impl Error for DoubtError {}
@@ -0,0 +1,198 @@
+use std::fmt;
+
+/// The pieces of a failure message, like ``Expected `v`=[1] to have len >= 2, but len = 1.``
+///
+/// Every macro of this crate builds one, so that tooling, custom reporters and other macros
+/// can inspect or reuse it; its `Display` implementation gives the message.
+/// ```
+/// use doubts::Failure;
+///
+/// let failure = Failure::new("v", "[1]", "have")
+///     .with_property("len")
+///     .with_operator(">=")
+///     .with_expected("2")
+///     .with_actual("1");
+/// assert_eq!(failure.to_string(), "Expected `v`=[1] to have len >= 2, but len = 1.");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Failure {
+    subject: String,
+    subject_debug: String,
+    negated: bool,
+    verb: String,
+    property: Option<String>,
+    operator: Option<String>,
+    expected: Option<String>,
+    actual: Option<String>,
+    reason: Option<String>,
+    detail: Option<String>,
+    context: Option<String>,
+}
+
+impl Failure {
+    /// A failure for the tested expression, as written in the test, and its `Debug` representation.
+    pub fn new(subject: impl Into<String>, subject_debug: impl Into<String>, verb: impl Into<String>) -> Failure {
+        Failure {
+            subject: subject.into(),
+            subject_debug: subject_debug.into(),
+            negated: false,
+            verb: verb.into(),
+            property: None,
+            operator: None,
+            expected: None,
+            actual: None,
+            reason: None,
+            detail: None,
+            context: None,
+        }
+    }
+
+    pub fn negated(mut self) -> Failure {
+        self.negated = true;
+        self
+    }
+
+    pub fn with_property(mut self, property: impl Into<String>) -> Failure {
+        self.property = Some(property.into());
+        self
+    }
+
+    pub fn with_operator(mut self, operator: impl Into<String>) -> Failure {
+        self.operator = Some(operator.into());
+        self
+    }
+
+    pub fn with_expected(mut self, expected: impl Into<String>) -> Failure {
+        self.expected = Some(expected.into());
+        self
+    }
+
+    pub fn with_actual(mut self, actual: impl Into<String>) -> Failure {
+        self.actual = Some(actual.into());
+        self
+    }
+
+    pub fn with_reason(mut self, reason: impl Into<String>) -> Failure {
+        self.reason = Some(reason.into());
+        self
+    }
+
+    pub fn with_context(mut self, context: impl Into<String>) -> Failure {
+        self.context = Some(context.into());
+        self
+    }
+
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Failure {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    /// The tested expression, as written in the test.
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+
+    /// The `Debug` representation of the tested value, empty when it has none, like closures.
+    pub fn subject_debug(&self) -> &str {
+        &self.subject_debug
+    }
+
+    /// Whether the expectation was negated with `not`.
+    pub fn is_negated(&self) -> bool {
+        self.negated
+    }
+
+    /// The verb of the expectation, like `be`, `have` or `contain`.
+    pub fn verb(&self) -> &str {
+        &self.verb
+    }
+
+    /// The checked property, like `empty` in `to be empty` or `len` in `to have len == 2`.
+    pub fn property(&self) -> Option<&str> {
+        self.property.as_deref()
+    }
+
+    /// The comparison operator, for `has` expectations.
+    pub fn operator(&self) -> Option<&str> {
+        self.operator.as_deref()
+    }
+
+    /// The expected value, or the arguments of the checked method.
+    pub fn expected(&self) -> Option<&str> {
+        self.expected.as_deref()
+    }
+
+    /// The actual value of the property, or of the tested value itself for direct comparisons.
+    pub fn actual(&self) -> Option<&str> {
+        self.actual.as_deref()
+    }
+
+    /// Why the expectation failed, when the actual value does not tell it alone, like `difference = 0.01`.
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+
+    /// The context given by the test, like `case 3` in `assert_that!(v, is_empty; "case {}", 3)`.
+    pub fn context(&self) -> Option<&str> {
+        self.context.as_deref()
+    }
+
+    /// Lines shown after the message, like the strings drawn with a caret under the char where they diverge.
+    pub fn detail(&self) -> Option<&str> {
+        self.detail.as_deref()
+    }
+
+    /// The expectation, as it follows "to" in the message, like `have len >= 2` or `not be empty`.
+    pub fn expectation(&self) -> String {
+        format!("{}{}", if self.negated { "not " } else { "" }, self.predicate())
+    }
+
+    /// Why the expectation failed, as it follows "but" in the message, like `len = 1`.
+    pub fn explanation(&self) -> Option<String> {
+        match (&self.property, &self.actual, &self.reason) {
+            (Some(property), Some(actual), Some(reason)) => Some(format!("{} = {} ({})", property, actual, reason)),
+            (Some(property), Some(actual), None) => Some(format!("{} = {}", property, actual)),
+            (_, _, Some(reason)) => Some(reason.clone()),
+            _ => None,
+        }
+    }
+
+    fn predicate(&self) -> String {
+        let mut predicate = self.verb.clone();
+        for piece in [&self.property, &self.operator, &self.expected].iter().filter_map(|piece| piece.as_ref()) {
+            predicate.push(' ');
+            predicate.push_str(piece);
+        }
+        predicate
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Expected `{}`", self.subject)?;
+        // the values without a `Debug` representation, like closures, are only shown as written
+        if !self.subject_debug.is_empty() {
+            write!(f, "={}", self.subject_debug)?;
+        }
+        write!(f, " {}to {}", if self.negated { "not " } else { "" }, self.predicate())?;
+        if let Some(explanation) = self.explanation() {
+            write!(f, ", but {}", explanation)?;
+        }
+        if let Some(context) = &self.context {
+            write!(f, "; {}", context)?;
+        }
+        write!(f, ".")?;
+        if let Some(detail) = &self.detail {
+            write!(f, "\n{}", detail)?;
+        }
+        #[cfg(feature = "pretty")]
+        {
+            if let (false, Some("=="), Some(expected), Some(actual)) = (self.negated, self.operator(), &self.expected, &self.actual) {
+                if expected.contains('\n') || actual.contains('\n') {
+                    write!(f, "\nDiff (- expected / + actual):\n{}", crate::diff::colored(expected, actual).trim_end())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
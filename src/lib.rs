@@ -1,51 +1,179 @@
+#[doc(hidden)]
+pub mod diff;
+#[doc(hidden)]
+pub mod assume;
+#[doc(hidden)]
+pub mod collections;
+#[doc(hidden)]
+pub mod approx;
+mod error;
+mod failure;
+pub mod matchers;
+#[doc(hidden)]
+pub mod maps;
+#[doc(hidden)]
+pub mod outcome;
+#[doc(hidden)]
+pub mod naming;
+#[doc(hidden)]
+pub mod panics;
+#[doc(hidden)]
+pub mod render;
+#[doc(hidden)]
+pub mod snapshots;
+#[doc(hidden)]
+pub mod soft;
+#[doc(hidden)]
+pub mod verdict;
+#[doc(hidden)]
+pub mod strings;
+#[cfg(test)]
+mod temporary;
+
+pub use crate::error::DoubtError;
+pub use crate::failure::Failure;
+pub use crate::matchers::Matcher;
+pub use crate::maps::MapLike;
+pub use crate::naming::Describe;
+
 /// Convenience, to write more explicit tests
 /// # Requirements
 /// The type of the expression that you are testing should derive "Debug"
 ///
+/// The expression is evaluated only once, and borrowed: the printed value is exactly the value that was checked.
+/// The expected values are borrowed too.
+/// ```
+/// # #[macro_use] extern crate doubts;
+/// # fn main() {
+/// let mut vectors = vec![vec![1, 2], vec![]].into_iter();
+/// assert_that!(vectors.next().unwrap(), has len == 2);
+/// assert_that!(vectors.next().unwrap(), is_empty);
+/// let expected = String::from("AB");
+/// assert_that!(String::from("ab"), has to_uppercase == expected);
+/// assert_that!(expected, has len == 2);
+/// # }
+/// ```
+/// The arguments of methods are evaluated once too, even when the check fails:
+/// ```
+/// # #[macro_use] extern crate doubts;
+/// # fn main() {
+/// let mut calls = 0;
+/// let mut next = || { calls += 1; calls };
+/// assert_that!(vec![1, 2], contains &next());
+/// let error = verify_that!(vec![1], contains &next()).unwrap_err();
+/// # #[cfg(not(feature = "pretty"))]
+/// assert_eq!(error.to_string(), "Expected `vec![1]`=[1] to contain 2.");
+/// assert_eq!(calls, 2);
+/// # }
+/// ```
+///
 /// # Check your own property
 /// You can write assert_that to check the value of a property (using an operator between the property name and the expected value)
 /// ```
-///  #[test]
-///  fn test() {
-///      let v = vec![1];
-///      assert_that!(v, has len <= 2)
-///  }
+/// # #[macro_use] extern crate doubts;
+/// # fn main() {
+/// let v = vec![1];
+/// assert_that!(v, has len <= 2)
+/// # }
 /// ```
 /// And if the specified property is wrong, a clean message would be printed. For example:
+/// ```should_panic
+/// # #[macro_use] extern crate doubts;
+/// # fn main() {
+/// let v = vec![1];
+/// assert_that!(v, has len >= 2)
+/// # }
 /// ```
-///  #[test]
-///  fn test() {
-///      let v = vec![1];
-///      assert_that!(v, has len >= 2)
-///  }
+/// would fail with a message like  ``Expected `v`=[1] to have len >= 2, but len = 1.``
+///
+/// The expected and actual values of the property are printed with their `Display` representation,
+/// or their `Debug` one for the types without `Display`, like the arguments of methods.
+/// With the `pretty` feature, their `Debug` representation is preferred, to be pretty-printed.
 /// ```
-/// would fail with a message like  ``Expected `vec![1]`=[1] to have len >= 2, but len = 1.``
+/// # #[macro_use] extern crate doubts;
+/// use std::fmt;
 ///
-/// This macro works with two conventions: methods names should follow name patterns like `is_${property}` and `${property}s(...)`
-/// That way, it is possible to have more readable messages.
+/// #[derive(PartialEq)]
+/// struct Celsius(i32);
 ///
-/// # Check an existing property for the struct
-/// First, the methods with name like `is_${property}()`, for example `is_empty` from Vec. These methods take 0 arguments, and returns as property.
-/// For example:
+/// impl fmt::Display for Celsius {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         write!(f, "{}°C", self.0)
+///     }
+/// }
+///
+/// #[derive(Debug)]
+/// struct Room { degrees: i32 }
+///
+/// impl Room {
+///     fn temperature(&self) -> Celsius {
+///         Celsius(self.degrees)
+///     }
+/// }
+///
+/// # fn main() {
+/// let room = Room { degrees: 20 };
+/// let error = verify_that!(room, has temperature == Celsius(25)).unwrap_err();
+/// # #[cfg(not(feature = "pretty"))]
+/// assert_eq!(error.to_string(), "Expected `room`=Room { degrees: 20 } to have temperature == 25°C, but temperature = 20°C.");
+/// # }
 /// ```
-///  #[test]
-///  fn test() {
-///      let v = vec![1];
-///      assert_that!(v, is_empty)
-///  }
+///
+/// The property can also be a public field, written with a leading dot, or a path of nested fields:
+/// ```should_panic
+/// # #[macro_use] extern crate doubts;
+/// #[derive(Debug)]
+/// struct Address { city: String }
+/// #[derive(Debug)]
+/// struct Person { name: String, address: Address }
+///
+/// # fn main() {
+/// let person = Person { name: "Ada".to_string(), address: Address { city: "Lyon".to_string() } };
+/// assert_that!(person, has .name == "Ada");
+/// assert_that!(person, has address.city == "Paris")
+/// # }
+/// ```
+/// would fail with a message like ``Expected `person`=Person { name: "Ada", address: Address { city: "Lyon" } } to have address.city == Paris, but address.city = Lyon.``
+///
+/// Finally, the property can be any chain of method calls, with arguments:
+/// ```should_panic
+/// # #[macro_use] extern crate doubts;
+/// # use std::collections::HashMap;
+/// # fn main() {
+/// let s = "doubts";
+/// assert_that!(s, has char_indices().count() == 6);
+/// assert_that!(s, has chars().rev().collect::<String>() == "stbuod");
+/// let mut map = HashMap::new();
+/// map.insert("a", 1);
+/// assert_that!(map, has get(&"k") == Some(&1))
+/// # }
+/// ```
+/// would fail with a message like ``Expected `map`={"a": 1} to have get(&"k") == Some(1), but get(&"k") = None.``
+///
+/// The messages read the names of the predicate methods as English, after "to": their first word is conjugated
+/// and their underscores become spaces, so that `is_empty` reads "be empty", `contains` "contain",
+/// `contains_key` "contain key", `has_children` "have children", `can_read` "be able to read" and `should_retry` "retry".
+///
+/// # Check an existing property for the struct
+/// First, the methods taking no argument and giving a bool, for example `is_empty` from Vec:
+/// ```should_panic
+/// # #[macro_use] extern crate doubts;
+/// # fn main() {
+/// let v = vec![1];
+/// assert_that!(v, is_empty)
+/// # }
 /// ```
 ///  Would fail with a message like ``Expected `v`=[1] to be empty.``
 ///
 /// # Check an existing property for the struct with a given list of arguments
-/// In the same way, any method telling if a property is right for a given list of arguments, should follow the pattern `${property}s(...)`, for example `contains` from Vec. These methods take at least one argument and give a bool as a result.
-/// For example:
+/// In the same way, the methods taking arguments and giving a bool, for example `contains` from Vec:
+/// ```should_panic
+/// # #[macro_use] extern crate doubts;
+/// # fn main() {
+/// let v = vec![1];
+/// assert_that!(v, contains &2)
+/// # }
 /// ```
-///  #[test]
-///  fn test() {
-///      let v = vec![1];
-///      assert_that!(v, contains &2)
-///  }
-///  ```
 /// Would fail with a message like ``Expected `v`=[1] to contain 2.``
 ///
 /// You can pass as much arguments as needed (at least one).
@@ -61,47 +189,1108 @@
 /// }
 /// ```
 /// You can now write the following test:
+/// ```should_panic
+/// # #[macro_use] extern crate doubts;
+/// # #[derive(Debug)]
+/// # struct Pair(i32,i32);
+/// # impl Pair{
+/// #     fn contains(&self, i:i32, j:i32) -> bool{
+/// #         false
+/// #     }
+/// # }
+/// # fn main() {
+/// let s = Pair(1, 2);
+/// assert_that!(s, contains 2, 3)
+/// # }
+/// ```
+/// Would fail with a message like ``Expected `s`=Pair(1, 2) to contain 2,3.``
+///
+/// The names of several words are conjugated the same way:
+/// ```
+/// # #[macro_use] extern crate doubts;
+/// use std::collections::HashMap;
+///
+/// # fn main() {
+/// let map: HashMap<&str, i32> = HashMap::new();
+/// let error = verify_that!(map, contains_key &"k").unwrap_err();
+/// # #[cfg(not(feature = "pretty"))]
+/// assert_eq!(error.to_string(), "Expected `map`={} to contain key k.");
+/// # }
+/// ```
+///
+/// When a method name does not read well, the type can word its predicates itself, by implementing [`Describe`](trait.Describe.html).
+///
+/// # Compare the value itself
+/// The value can also be compared directly, with one of the operators `==`, `!=`, `<`, `<=`, `>` and `>=`.
+/// ```should_panic
+/// # #[macro_use] extern crate doubts;
+/// # fn main() {
+/// let x = 3;
+/// assert_that!(x, != 5);
+/// assert_that!(x, >= 5)
+/// # }
+/// ```
+/// Would fail with a message like ``Expected `x`=3 to be >= 5.``
+///
+/// # Compare floating-point numbers
+/// Floating-point numbers, and floating-point properties, can be compared with a tolerance:
+/// an absolute one, a relative one with `relative`, or a number of units in the last place with `ulps`.
+/// ```should_panic
+/// # #[macro_use] extern crate doubts;
+/// # fn main() {
+/// let x = 0.1 + 0.2;
+/// assert_that!(x, is close to 0.3 within 1e-9);
+/// assert_that!(x, is close to 0.3 within relative 1e-12);
+/// assert_that!(x, is close to 0.3 within ulps 1);
+/// let v = vec![0.1, 0.2];
+/// assert_that!(v, has iter().fold(0.0, |sum, x| sum + x) ~= 0.31 within 1e-9)
+/// # }
+/// ```
+/// Would fail with a message like ``Expected `v`=[0.1, 0.2] to have iter().fold(0.0, |sum, x| sum + x) ~= 0.31 within 1e-9, but iter().fold(0.0, |sum, x| sum + x) = 0.30000000000000004 (difference = 0.009999999999999953).``
+///
+/// # Use matchers
+/// Reusable expectations can be written as [`Matcher`](trait.Matcher.html)s, and checked with `satisfies`.
+/// The [`matchers`](matchers/index.html) module provides some, like `eq`, `gt`, `contains`, `all_of`, `any_of` and `not`.
+/// ```should_panic
+/// # #[macro_use] extern crate doubts;
+/// use doubts::matchers::{any_of, eq, gt};
+///
+/// # fn main() {
+/// let x = 3;
+/// assert_that!(x, satisfies any_of((eq(0), gt(5))))
+/// # }
+/// ```
+/// Would fail with a message like ``Expected `x`=3 to be equal to 0 or be greater than 5.``
+///
+/// # Check the elements of a collection
+/// Collections, or anything whose reference can be iterated, have dedicated checks, whose messages tell the offending elements by index:
+/// `contains_all`, `contains_exactly` (optionally `in any order`), `any` and `none` followed by
+/// a predicate method or a comparison, `is_sorted` and `is_sorted_by_key`.
+/// Slices and iterators have them too: their elements are iterated from a copy.
+///
+/// `has same elements as` compares with any other collection, in any order but with as many duplicates,
+/// and only needs the elements to be comparable with `==`. Its message lists the missing and extra elements,
+/// sorted by their `Debug` representation; the value itself is shown as its `Debug` renders it, in an unspecified
+/// order for a `HashSet`:
+/// ```
+/// # #[macro_use] extern crate doubts;
+/// use std::collections::HashSet;
+///
+/// # fn main() {
+/// let tags: HashSet<&str> = vec!["b", "a"].into_iter().collect();
+/// assert_that!(tags, has same elements as ["a", "b"]);
+/// let error = verify_that!(tags, has same elements as ["c"]).unwrap_err();
+/// assert!(error.to_string().ends_with("but missing \"c\"; extra \"a\", \"b\"."));
+/// let v = vec![1, 1, 2, 4];
+/// let slice: &[i32] = &v;
+/// assert_that!(slice, has same elements as [4, 2, 1, 1]);
+/// let error = verify_that!(v, has same elements as vec![2, 1, 3, 3]).unwrap_err();
+/// # #[cfg(not(feature = "pretty"))]
+/// assert_eq!(error.to_string(), "Expected `v`=[1, 1, 2, 4] to have the same elements as [2, 1, 3, 3], \
+///     but missing 3 (2 times); extra 1, 4.");
+/// let error = verify_that!(v.iter(), has not same elements as [1, 2, 4, 1]).unwrap_err();
+/// # #[cfg(not(feature = "pretty"))]
+/// assert_eq!(error.to_string(), "Expected `v.iter()`=Iter([1, 1, 2, 4]) not to have the same elements as [1, 2, 4, 1].");
+/// # }
+/// ```
+///
+/// With `each`, any expectation of this macro is checked on every element, and all the failing ones are reported.
+/// ```
+/// # #[macro_use] extern crate doubts;
+/// # fn main() {
+/// let v: Vec<i32> = vec![3, 1, 2];
+/// assert_that!(v, contains_all [1, 2]);
+/// assert_that!(v, contains_exactly [1, 2, 3] in any order);
+/// assert_that!(v, any > 2);
+/// assert_that!(v, none is_negative);
+/// assert_that!(v, each >= 1);
+/// let words = vec!["a", "", "bc", ""];
+/// let error = verify_that!(words, each has len > 0).unwrap_err();
+/// # #[cfg(not(feature = "pretty"))]
+/// assert_eq!(error.to_string(), "Expected `words`=[\"a\", \"\", \"bc\", \"\"] to have each element have len > 0, \
+///     but it fails for [1] = \"\" (len = 0), [3] = \"\" (len = 0).");
+/// let error = verify_that!(v, contains_exactly [1, 2, 3]).unwrap_err();
+/// # #[cfg(not(feature = "pretty"))]
+/// assert_eq!(error.to_string(), "Expected `v`=[3, 1, 2] to contain exactly [1, 2, 3], \
+///     but [0] = 3 instead of 1; [1] = 1 instead of 2; [2] = 2 instead of 3.");
+/// let error = verify_that!(v, none is_positive).unwrap_err();
+/// # #[cfg(not(feature = "pretty"))]
+/// assert_eq!(error.to_string(), "Expected `v`=[3, 1, 2] to have no element be positive, but it holds for [0] = 3, [1] = 1, [2] = 2.");
+/// let error = verify_that!(v, is_sorted).unwrap_err();
+/// # #[cfg(not(feature = "pretty"))]
+/// assert_eq!(error.to_string(), "Expected `v`=[3, 1, 2] to be sorted, but [0] = 3 comes before [1] = 1.");
+/// let error = verify_that!(v, is_sorted_by_key |x: &i32| -x).unwrap_err();
+/// # #[cfg(not(feature = "pretty"))]
+/// assert_eq!(error.to_string(), "Expected `v`=[3, 1, 2] to be sorted by key |x: &i32| -x, but [1] = 1 (key -1) comes before [2] = 2 (key -2).");
+/// # }
+/// ```
+///
+/// The elements are not moved: the forms giving back a value, like `is_some then`, check a reference to each element.
+/// ```
+/// # #[macro_use] extern crate doubts;
+/// # fn main() {
+/// let names = vec![Some("ada".to_string()), Some(String::new())];
+/// assert_that!(names, each is_some);
+/// assert_that!(names, each is_some_and |name: &String| name.is_ascii());
+/// let error = verify_that!(names, each is_some then has len == 3).unwrap_err();
+/// # #[cfg(not(feature = "pretty"))]
+/// assert_eq!(error.to_string(), "Expected `names`=[Some(\"ada\"), Some(\"\")] to have each element be some then have len == 3, \
+///     but it fails for [1] = Some(\"\") (len = 0).");
+/// # }
+/// ```
+///
+/// # Check maps
+/// `HashMap`s, `BTreeMap`s and the other [`MapLike`](trait.MapLike.html) values have dedicated checks: `has_key`,
+/// `has_entry` followed by a key, `=>` and a value, `has_keys` (optionally `exactly`) and `has_entries exactly`.
+/// Their messages tell the missing, unexpected and different entries, and show the maps in their order,
+/// or sorted by key for the maps having none like `HashMap`s.
+/// ```
+/// # #[macro_use] extern crate doubts;
+/// use std::collections::{BTreeMap, HashMap};
+///
+/// # fn main() {
+/// let ages: HashMap<&str, u32> = vec![("bob", 31), ("alice", 27), ("carol", 45)].into_iter().collect();
+/// assert_that!(ages, has_key "alice");
+/// assert_that!(ages, has_entry "bob" => 31);
+/// assert_that!(ages, has_keys ["alice", "bob"]);
+/// let expected: HashMap<&str, u32> = vec![("alice", 28), ("bob", 31), ("dave", 50)].into_iter().collect();
+/// let error = verify_that!(ages, has_entries exactly expected).unwrap_err();
+/// # #[cfg(not(feature = "pretty"))]
+/// assert_eq!(error.to_string(), "Expected `ages`={\"alice\": 27, \"bob\": 31, \"carol\": 45} to have exactly the entries \
+///     {\"alice\": 28, \"bob\": 31, \"dave\": 50}, but missing \"dave\" => 50; unexpected \"carol\" => 45; \"alice\" => 27 instead of 28.");
+/// let error = verify_that!(ages, has_entry "bob" => 32).unwrap_err();
+/// # #[cfg(not(feature = "pretty"))]
+/// assert_eq!(error.to_string(), "Expected `ages`={\"alice\": 27, \"bob\": 31, \"carol\": 45} to have entry \"bob\" => 32, \
+///     but it has \"bob\" => 31.");
+/// let error = verify_that!(ages, has_keys ["alice", "dave"]).unwrap_err();
+/// # #[cfg(not(feature = "pretty"))]
+/// assert_eq!(error.to_string(), "Expected `ages`={\"alice\": 27, \"bob\": 31, \"carol\": 45} to have all of the keys \
+///     [\"alice\", \"dave\"], but missing \"dave\".");
+///
+/// // the maps with an order keep it
+/// let ids: BTreeMap<u32, &str> = (1..=11).map(|id| (id, "x")).collect();
+/// let error = verify_that!(ids, has_key 12).unwrap_err();
+/// # #[cfg(not(feature = "pretty"))]
+/// assert_eq!(error.to_string(), "Expected `ids`={1: \"x\", 2: \"x\", 3: \"x\", 4: \"x\", 5: \"x\", 6: \"x\", 7: \"x\", 8: \"x\", \
+///     9: \"x\", 10: \"x\", 11: \"x\"} to have key 12.");
+/// # }
+/// ```
+///
+/// # Check options and results
+/// `is_ok`, `is_err`, `is_some` and `is_none` tell the content of the value when they fail.
+/// ```should_panic
+/// # #[macro_use] extern crate doubts;
+/// # fn main() {
+/// let r: Result<Vec<i32>, String> = Err("empty input".to_string());
+/// assert_that!(r, is_ok)
+/// # }
+/// ```
+/// Would fail with a message like ``Expected `r`=Err("empty input") to be ok, but it is an error: "empty input".``
+///
+/// Like `is_sorted`, `is_sorted_by_key` and `has_key`, they call the method of the other types having one, as any predicate method.
+/// ```
+/// # #[macro_use] extern crate doubts;
+/// #[derive(Debug)]
+/// enum Status {
+///     Found,
+///     NotFound,
+/// }
+///
+/// impl Status {
+///     fn is_ok(&self) -> bool {
+///         matches!(self, Status::Found)
+///     }
+/// }
+///
+/// # fn main() {
+/// let status = Status::NotFound;
+/// let error = verify_that!(status, is_ok).unwrap_err();
+/// assert_eq!(error.to_string(), "Expected `status`=NotFound to be ok.");
+/// # }
+/// ```
+///
+/// The inner value can also be checked, with `is_ok_with`, `is_some_and` (taking a predicate), `is_err_matching`
+/// (taking a pattern, and an optional guard), or by chaining any expectation of this macro after `is_ok then`, `is_err then` or `is_some then`.
+/// These forms give back the inner value, for further use: like `unwrap`, they take the tested value,
+/// so pass a reference to keep it.
+/// ```
+/// # #[macro_use] extern crate doubts;
+/// # fn main() {
+/// let r: Result<Vec<i32>, String> = Ok(vec![1, 2, 3]);
+/// let v = assert_that!(&r, is_ok then has len == 3);
+/// assert_that!(v, contains &2);
+/// assert_that!(r, is_ok_with vec![1, 2, 3]);
+/// assert_that!(Some(3), is_some_and |x: &i32| *x > 2);
+/// assert_that!("x".parse::<i32>(), is_err_matching e if e.to_string().contains("invalid"));
+/// # }
+/// ```
+/// Chained after `then`, the forms giving back a value get a reference to the inner value,
+/// so that they can be chained on values that are not `Copy`.
+/// ```
+/// # #[macro_use] extern crate doubts;
+/// # fn main() {
+/// let r: Result<Option<String>, ()> = Ok(Some("a".to_string()));
+/// assert_that!(&r, is_ok then is_some then has len == 1);
+/// assert_that!(&r, is_ok then is_some_and |s: &String| s == "a");
+/// let error = verify_that!(r, is_ok then is_some then has len == 2).unwrap_err();
+/// # #[cfg(not(feature = "pretty"))]
+/// assert_eq!(error.to_string(), "Expected `r`=Ok(Some(\"a\")) to be ok then be some then have len == 2, but len = 1.");
+/// # }
 /// ```
-/// #[test]
-/// fn test() {
-///     let s = Pair(1, 2);
-///     assert_that!(s, contains 2, 3)
+///
+/// # Check strings
+/// Strings, or anything that can be seen as one, have dedicated checks: `starts with`, `ends with`,
+/// `contains substring`, `equals ignoring case` and, with the `regex` feature, `matches regex`.
+/// Their messages point at the char where the strings diverge.
+/// ```
+/// # #[macro_use] extern crate doubts;
+/// # fn main() {
+/// let s = String::from("hello world");
+/// assert_that!(s, ends with "world");
+/// assert_that!(s, contains substring "o w");
+/// assert_that!(s, equals ignoring case "Hello World");
+/// let error = verify_that!(s, starts with "help").unwrap_err();
+/// assert_eq!(error.to_string(), "Expected `s`=\"hello world\" to start with \"help\", but it differs at char 3.
+///   \"hello world\"
+///   \"help\"
+///       ^");
+/// # }
+/// ```
+/// The strings are aligned on their last chars for `ends with`, and on the closest match for `contains substring`:
+/// ```
+/// # #[macro_use] extern crate doubts;
+/// # fn main() {
+/// let s = String::from("hello world");
+/// let error = verify_that!(s, ends with "word").unwrap_err();
+/// assert_eq!(error.to_string(), "Expected `s`=\"hello world\" to end with \"word\", but it differs at char 9.
+///   \"hello world\"
+///          \"word\"
+///             ^");
+/// let error = verify_that!(s, contains substring "low w").unwrap_err();
+/// assert_eq!(error.to_string(), "Expected `s`=\"hello world\" to contain substring \"low w\", but the closest match differs at char 5.
+///   \"hello world\"
+///      \"low w\"
+///         ^");
+/// let error = verify_that!(s, equals ignoring case "Hello Word").unwrap_err();
+/// assert_eq!(error.to_string(), "Expected `s`=\"hello world\" to equal ignoring case \"Hello Word\", but it differs at char 9.
+///   \"hello world\"
+///   \"Hello Word\"
+///             ^");
+/// # }
+/// ```
+///
+/// # Compare with snapshots
+/// Big values can be compared with their `{:#?}` rendering stored by a previous run, with `matches snapshot` and a name.
+/// ```no_run
+/// # #[macro_use] extern crate doubts;
+/// # fn main() {
+/// let config = vec![("name", "doubts"), ("edition", "2018")];
+/// assert_that!(config, matches snapshot "config")
+/// # }
+/// ```
+/// The first run writes the snapshot `snapshots/<test file>__config.snap` beside the test file.
+/// The next runs fail when the rendering differs from it, with a line diff, and write the new rendering beside it
+/// with the `.snap.new` extension. Run the tests with `DOUBTS_UPDATE=1` to rewrite the differing snapshots.
+///
+/// # Check panics
+/// A closure can be checked to panic with `panics`, `panics with` a text its message contains,
+/// or, with the `regex` feature, `panics matching` a regular expression. The panic is not printed.
+/// `does not panic` gives back the result of the closure.
+/// ```should_panic
+/// # #[macro_use] extern crate doubts;
+/// fn parse(input: &str) -> i32 {
+///     if input.is_empty() {
+///         panic!("empty input");
+///     }
+///     input.parse().expect("not a number")
 /// }
+///
+/// # fn main() {
+/// assert_that!(|| parse(""), panics with "empty input");
+/// let x = assert_that!(|| parse("12"), does not panic);
+/// assert_that!(x, == 12);
+/// assert_that!(|| parse("x"), panics with "empty")
+/// # }
+/// ```
+/// Would fail with a message like ``Expected `|| parse("x")` to panic with "empty", but it panicked with "not a number: ParseIntError { kind: InvalidDigit }".``
+///
+/// # Match a pattern
+/// Any pattern can be checked with `matches`, with an optional guard.
+/// ```should_panic
+/// # #[macro_use] extern crate doubts;
+/// #[derive(Debug)]
+/// enum Event { Open { id: u32 }, Closed }
+///
+/// # fn main() {
+/// let ev = Event::Closed;
+/// assert_that!(ev, matches Event::Closed | Event::Open { .. });
+/// assert_that!(ev, matches Event::Open { id } if *id > 0)
+/// # }
+/// ```
+/// Would fail with a message like ``Expected `ev`=Closed to match Event::Open { id } if *id > 0.``
+///
+/// To use the variables bound by the pattern, follow it with `=>` and an expression: its value is given back.
+/// Like `unwrap`, this form takes the tested value, so pass a reference to keep it.
+/// ```
+/// # #![deny(warnings)]
+/// # #[macro_use] extern crate doubts;
+/// # #[derive(Debug)]
+/// # enum Event { Open { id: u32 }, Closed }
+/// # fn main() {
+/// let ev = Event::Open { id: 3 };
+/// let id = assert_that!(&ev, matches Event::Open { id } => *id);
+/// assert_that!(id, == 3);
+/// let id = assert_that!(ev, matches Event::Open { id } if id > 0 => id);
+/// assert_that!(id, == 3);
+/// # }
+/// ```
+///
+/// # Negate any check
+/// Every form can be negated with the `not` keyword: put it in front of the method name, or right after `has`.
+/// ```
+/// # #[macro_use] extern crate doubts;
+/// # fn main() {
+/// let v = vec![1];
+/// assert_that!(v, not is_empty);
+/// assert_that!(v, not contains &2);
+/// assert_that!(v, has not len == 0);
+/// # }
+/// ```
+/// The messages are written in the negated voice. For example:
+/// ```should_panic
+/// # #[macro_use] extern crate doubts;
+/// # fn main() {
+/// let v: Vec<i32> = vec![];
+/// assert_that!(v, not is_empty)
+/// # }
+/// ```
+/// Would fail with a message like ``Expected `v`=[] not to be empty.``
+///
+/// # Add context
+/// Like `assert!`, every form can be followed by `;` and a format string with its arguments, to tell which case failed,
+/// for example in loops or table-driven tests. It is only formatted when the check fails.
+/// ```should_panic
+/// # #[macro_use] extern crate doubts;
+/// # fn main() {
+/// for (path, lines) in vec![("a.txt", vec!["x"]), ("b.txt", vec![])] {
+///     assert_that!(lines, not is_empty; "after loading {}", path)
+/// }
+/// # }
+/// ```
+/// Would fail with a message like ``Expected `lines`=[] not to be empty; after loading b.txt.``
+///
+/// To find the `;`, the expectation after the tested value is read one token at a time, a bracketed group counting as one token:
+/// an expectation longer than about 120 tokens needs a higher `#![recursion_limit]` in the crate using it.
+///
+/// # Pretty output
+/// With the `pretty` feature, the values are pretty-printed with `{:#?}`, and when a comparison with `==`
+/// fails on multi-line values, a line-by-line colored diff between the expected and the actual values is shown.
+/// For example, `assert_that!(config, has .address == Address { city: "Paris".to_string() })` would fail with:
+/// ```text
+/// Expected `config`=Config {
+///     name: "x",
+///     address: Address {
+///         city: "Lyon",
+///     },
+/// } to have address == Address {
+///     city: "Paris",
+/// }, but address = Address {
+///     city: "Lyon",
+/// }.
+/// Diff (- expected / + actual):
+///  Address {
+/// -    city: "Paris",
+/// +    city: "Lyon",
+///  }
 /// ```
-/// Would fail with a message like ``Expected `s`=Pair(1, 2) to contain 2,3.``
-
 #[macro_export]
 macro_rules! assert_that {
-    ($e: expr, has $i:ident $tt:tt $n: expr) => {
-        {
-            let x = $e.$i();
-            assert!(x $tt $n, "Expected `{}`={:?} to have {} {} {}, but {} = {}.",
-                stringify!($e), $e,
-                stringify!($i), stringify!($tt), $n,
-                stringify!($i), x)
+    ($($t:tt)*) => {
+        match $crate::__doubts_check!(@context $($t)*) {
+            Ok(value) => value,
+            Err(failure) => panic!("{}", failure),
         }
     };
-    ($e: expr, $i:ident) => {
+}
+
+/// Soft version of [`assert_that!`](macro.assert_that.html), with the same grammar and the same messages.
+///
+/// Inside an [`assert_all!`](macro.assert_all.html) block, a failing `check_that!` does not stop the test:
+/// the failure is recorded, and the block panics once at its end with every recorded failure.
+/// Outside of such a block, `check_that!` behaves like `assert_that!`.
+/// ```should_panic
+/// # #[macro_use] extern crate doubts;
+/// # fn main() {
+/// let v = vec![1];
+/// assert_all! {
+///     check_that!(v, is_empty);
+///     check_that!(v, has len == 1);
+///     check_that!(v, contains &2);
+/// }
+/// # }
+/// ```
+/// Would fail with a message like
+/// ```text
+/// 2 expectations failed:
+/// 1. Expected `v`=[1] to be empty.
+/// 2. Expected `v`=[1] to contain 2.
+/// ```
+#[macro_export]
+macro_rules! check_that {
+    ($($t:tt)*) => {
+        if let Err(failure) = $crate::__doubts_check!(@context $($t)*) {
+            $crate::soft::record(failure.to_string())
+        }
+    };
+}
+
+/// Runs a block of code, collecting the failures of every [`check_that!`](macro.check_that.html) inside it,
+/// then panics with a numbered list of them if there was at least one.
+///
+/// The failures are collected for the current thread, so `check_that!` can also be called from functions used in the block.
+/// Blocks can be nested: a failing inner block is reported as a single failure by the outer one.
+/// When the block panics, for example on a failing `assert_that!`, the failures recorded before are printed.
+#[macro_export]
+macro_rules! assert_all {
+    ($($body:tt)*) => {
         {
-            let method_name = stringify!($i);
-             {
-                assert!($e.$i(), "Expected `{}`={:?} to be {}.",
-                    stringify!($e), $e,
-                    if method_name.starts_with("is_") {&method_name[3..]}else{method_name}
-                )
+            let scope = $crate::soft::Scope::enter();
+            {
+                $($body)*
+            };
+            scope.finish();
+        }
+    };
+}
+
+/// Non-panicking version of [`assert_that!`](macro.assert_that.html), with the same grammar.
+///
+/// It returns a `Result<(), DoubtError>`, so that a test returning a `Result` can use `?`,
+/// and a harness can inspect the pieces of the failure message.
+/// ```
+/// # #[macro_use] extern crate doubts;
+/// use doubts::DoubtError;
+///
+/// fn check(name: &str) -> Result<(), DoubtError> {
+///     verify_that!(name, not is_empty)?;
+///     verify_that!(name, has len <= 5)?;
+///     Ok(())
+/// }
+///
+/// # fn main() {
+/// let error = check("doubts!").unwrap_err();
+/// assert_eq!(error.failure().property(), Some("len"));
+/// assert_eq!(error.failure().actual(), Some("7"));
+/// assert_eq!(error.to_string(), "Expected `name`=\"doubts!\" to have len <= 5, but len = 7.");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! verify_that {
+    ($($t:tt)*) => {
+        $crate::__doubts_check!(@context $($t)*).map_err($crate::DoubtError::from)
+    };
+}
+
+/// Version of [`assert_that!`](macro.assert_that.html) checked only in debug builds, like `debug_assert!`,
+/// with the same grammar and the same messages.
+///
+/// In release builds, the expression is not evaluated, so that it can check invariants in production code.
+/// ```should_panic
+/// # #[macro_use] extern crate doubts;
+/// fn average(values: &[f64]) -> f64 {
+///     debug_assert_that!(values, not is_empty);
+///     values.iter().sum::<f64>() / values.len() as f64
+/// }
+///
+/// # fn main() {
+/// # if !cfg!(debug_assertions) { panic!() }
+/// average(&[]);
+/// # }
+/// ```
+/// Would fail in debug builds with a message like ``Expected `values`=[] not to be empty.``
+#[macro_export]
+macro_rules! debug_assert_that {
+    ($($t:tt)*) => {
+        if cfg!(debug_assertions) {
+            $crate::assert_that!($($t)*);
+        }
+    };
+}
+
+/// Version of [`assert_that!`](macro.assert_that.html) for the conditions a test depends on, with the same grammar.
+///
+/// When the expectation fails, the reason is printed and the current function returns early,
+/// so that the test passes without running its remaining part. The function should return `()`,
+/// or `Result<(), E>` and then returns `Ok(())`. Like `assert_that!`, it gives back the inner value of the forms that have one.
+/// ```
+/// # #[macro_use] extern crate doubts;
+/// fn database_test() {
+///     let url = assume_that!(std::env::var("DOUBTS_DATABASE_URL"), is_ok then not is_empty);
+///     // connects to the database...
+///     # let _ = url;
+///     unreachable!();
+/// }
+///
+/// # fn main() {
+/// # std::env::remove_var("DOUBTS_DATABASE_URL");
+/// database_test();
+/// # }
+/// ```
+/// Prints a message like ``Skipped: Expected `std::env::var("DOUBTS_DATABASE_URL")`=Err(NotPresent) to be ok, but it is an error: NotPresent.``
+#[macro_export]
+macro_rules! assume_that {
+    ($($t:tt)*) => {
+        match $crate::__doubts_check!(@context $($t)*) {
+            Ok(value) => value,
+            Err(failure) => {
+                eprintln!("Skipped: {}", failure);
+                return $crate::assume::Skip::skipped();
             }
         }
     };
-    ($e: expr, $i:ident $($n: expr),+) => {
-        {
-            let method_name = stringify!($i);
-             {
-                assert!($e.$i($($n),+), "Expected `{}`={:?} to {} {}.",
-                    stringify!($e), $e,
-                    if method_name.ends_with('s'){&method_name[..method_name.len()-1]}else{method_name},
-                    &([$($n.to_string()),+]).join(",")
-                )
+}
+
+/// The checks with regular expressions, which exist only with the `regex` feature of this crate,
+/// whatever the features of the crate using them.
+#[cfg(feature = "regex")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __doubts_regex {
+    (@panics $e: expr, $pattern: expr) => {
+        match &$pattern {
+            pattern => $crate::panics::panics(stringify!($e), $e, $crate::panics::Expectation::Matching(pattern.as_ref()))
+        }
+    };
+    (@matches $e: expr, $negated: expr, $pattern: expr) => {
+        match (&$e, &$pattern) {
+            (subject, pattern) => $crate::strings::regex(stringify!($e), subject, pattern, $negated)
+        }
+    };
+}
+
+#[cfg(not(feature = "regex"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __doubts_regex {
+    (@panics $($t:tt)*) => { compile_error!("`panics matching` requires the `regex` feature of doubts") };
+    (@matches $($t:tt)*) => { compile_error!("`matches regex` requires the `regex` feature of doubts") };
+}
+
+// Some checks depend on what the tested value implements, which a macro cannot see. They call a method on
+// a reference to a wrapper, like `(&Checked(subject)).state()`, that two traits in scope implement: the preferred one
+// for the wrapper itself, and the fallback for a reference to the wrapper. Method resolution tries the receiver before
+// borrowing it once more, so the preferred implementation is used when its bounds hold, and the fallback otherwise.
+// The wrappers are `collections::Elements`, `outcome::Checked`, `naming::Subject`, `maps::Map`, `render::Shown`, and
+// `matchers::Satisfied`, which has a third level for the double negations. `collections::Sortable` and `maps::HasKey`
+// rely on the same resolution, the methods of the value itself being preferred to theirs.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __doubts_check {
+    // the tested expression is parsed at once, and only the expectation is read token by token, up to the `;`
+    (@context $e: expr, $($expectation:tt)+) => { $crate::__doubts_check!(@expectation $e, [] $($expectation)+) };
+    (@expectation $e: expr, [$($check:tt)*] ; $($context:tt)+) => {
+        $crate::__doubts_check!($e, $($check)*).map_err(|failure| Box::new(failure.with_context(format!($($context)+))))
+    };
+    (@expectation $e: expr, [$($check:tt)*] $next:tt $($rest:tt)*) => { $crate::__doubts_check!(@expectation $e, [$($check)* $next] $($rest)*) };
+    (@expectation $e: expr, [$($check:tt)*]) => { $crate::__doubts_check!($e, $($check)*) };
+    (@compare $e: expr, $tt:tt $n: expr) => {
+        match (&$e, &$n) {
+            (subject, expected) => if *subject $tt *expected {
+                Ok(())
+            } else {
+                let subject_debug = $crate::render::debug(subject);
+                Err(Box::new($crate::Failure::new(stringify!($e), subject_debug.clone(), "be")
+                    .with_operator(stringify!($tt))
+                    .with_expected($crate::render::debug(expected))
+                    .with_actual(subject_debug)))
+            }
+        }
+    };
+    (@has $e: expr, $not:tt [$($property:tt)+] == $n: expr) => { $crate::__doubts_check!(@has_compare $e, $not [$($property)+] == $n) };
+    (@has $e: expr, $not:tt [$($property:tt)+] != $n: expr) => { $crate::__doubts_check!(@has_compare $e, $not [$($property)+] != $n) };
+    (@has $e: expr, $not:tt [$($property:tt)+] < $n: expr) => { $crate::__doubts_check!(@has_compare $e, $not [$($property)+] < $n) };
+    (@has $e: expr, $not:tt [$($property:tt)+] <= $n: expr) => { $crate::__doubts_check!(@has_compare $e, $not [$($property)+] <= $n) };
+    (@has $e: expr, $not:tt [$($property:tt)+] > $n: expr) => { $crate::__doubts_check!(@has_compare $e, $not [$($property)+] > $n) };
+    (@has $e: expr, $not:tt [$($property:tt)+] >= $n: expr) => { $crate::__doubts_check!(@has_compare $e, $not [$($property)+] >= $n) };
+    (@has $e: expr, $not:tt [$($property:tt)+] ~ = $($rest:tt)+) => { $crate::__doubts_check!(@close $e, $not [$($property)+] [] $($rest)+) };
+    (@has $e: expr, $not:tt [$($property:tt)*] :: < $($rest:tt)+) => { $crate::__doubts_check!(@generics $e, $not [$($property)* :: <] [<] $($rest)+) };
+    (@has $e: expr, $not:tt [$($property:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::__doubts_check!(@has $e, $not [$($property)* $next] $($rest)*)
+    };
+    (@has_compare $e: expr, [not] $property:tt $tt:tt $n: expr) => {
+        $crate::__doubts_check!(@has_compare $e, true, $property $tt $n)
+    };
+    (@has_compare $e: expr, [] $property:tt $tt:tt $n: expr) => {
+        $crate::__doubts_check!(@has_compare $e, false, $property $tt $n)
+    };
+    (@has_compare $e: expr, $negated:expr, [$($property:tt)+] $tt:tt $n: expr) => {
+        match (&$e, &$n) {
+            (subject, expected) => match $crate::__doubts_check!(@property subject, $($property)+) {
+                ref actual => if (*actual $tt *expected) != $negated {
+                    Ok(())
+                } else {
+                    let failure = $crate::Failure::new(stringify!($e), $crate::render::debug(subject), "have")
+                        .with_property($crate::__doubts_check!(@property_name $($property)+))
+                        .with_operator(stringify!($tt))
+                        .with_expected($crate::__doubts_check!(@shown expected))
+                        .with_actual($crate::__doubts_check!(@shown actual));
+                    Err(Box::new(if $negated { failure.negated() } else { failure }))
+                }
+            }
+        }
+    };
+    (@generics $e: expr, $not:tt [$($property:tt)*] [] $($rest:tt)*) => { $crate::__doubts_check!(@has $e, $not [$($property)*] $($rest)*) };
+    (@generics $e: expr, $not:tt [$($property:tt)*] [$($depth:tt)*] < $($rest:tt)+) => { $crate::__doubts_check!(@generics $e, $not [$($property)* <] [< $($depth)*] $($rest)+) };
+    (@generics $e: expr, $not:tt [$($property:tt)*] [< $($depth:tt)*] > $($rest:tt)*) => { $crate::__doubts_check!(@generics $e, $not [$($property)* >] [$($depth)*] $($rest)*) };
+    (@generics $e: expr, $not:tt [$($property:tt)*] [< < $($depth:tt)*] >> $($rest:tt)*) => { $crate::__doubts_check!(@generics $e, $not [$($property)* >>] [$($depth)*] $($rest)*) };
+    (@generics $e: expr, $not:tt [$($property:tt)*] $depth:tt $next:tt $($rest:tt)*) => { $crate::__doubts_check!(@generics $e, $not [$($property)* $next] $depth $($rest)*) };
+    (@close $e: expr, $not:tt $property:tt [$($expected:tt)+] within relative $epsilon: expr) => {
+        $crate::__doubts_check!(@approx $e, $not $property ($($expected)+) $crate::approx::Tolerance::Relative($epsilon))
+    };
+    (@close $e: expr, $not:tt $property:tt [$($expected:tt)+] within ulps $ulps: expr) => {
+        $crate::__doubts_check!(@approx $e, $not $property ($($expected)+) $crate::approx::Tolerance::Ulps($ulps))
+    };
+    (@close $e: expr, $not:tt $property:tt [$($expected:tt)+] within $epsilon: expr) => {
+        $crate::__doubts_check!(@approx $e, $not $property ($($expected)+) $crate::approx::Tolerance::Absolute($epsilon))
+    };
+    (@close $e: expr, $not:tt $property:tt [$($expected:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::__doubts_check!(@close $e, $not $property [$($expected)* $next] $($rest)*)
+    };
+    (@close $e: expr, $not:tt $property:tt [$($expected:tt)*]) => {
+        compile_error!("expected a tolerance after the expected value, like `within 1e-9`, `within relative 1e-6` or `within ulps 4`")
+    };
+    (@approx $e: expr, [not] $property:tt $expected:tt $tolerance: expr) => {
+        $crate::__doubts_check!(@approx $e, true, $property $expected $tolerance)
+    };
+    (@approx $e: expr, [] $property:tt $expected:tt $tolerance: expr) => {
+        $crate::__doubts_check!(@approx $e, false, $property $expected $tolerance)
+    };
+    (@approx $e: expr, $negated:expr, [] ($($expected:tt)+) $tolerance: expr) => {
+        match (&$e, $($expected)+, $tolerance) {
+            (subject, expected, tolerance) => {
+                let (close, difference) = $crate::approx::close(*subject, expected, tolerance);
+                if close != $negated {
+                    Ok(())
+                } else {
+                    let failure = $crate::Failure::new(stringify!($e), $crate::render::debug(subject), "be close to")
+                        .with_expected(format!("{} within {}", $crate::render::debug(&expected), tolerance))
+                        .with_reason(difference);
+                    Err(Box::new(if $negated { failure.negated() } else { failure }))
+                }
+            }
+        }
+    };
+    (@approx $e: expr, $negated:expr, [$($property:tt)+] ($($expected:tt)+) $tolerance: expr) => {
+        match (&$e, $($expected)+, $tolerance) {
+            (subject, expected, tolerance) => match $crate::__doubts_check!(@property subject, $($property)+) {
+                ref actual => {
+                    let (close, difference) = $crate::approx::close(*actual, expected, tolerance);
+                    if close != $negated {
+                        Ok(())
+                    } else {
+                        let failure = $crate::Failure::new(stringify!($e), $crate::render::debug(subject), "have")
+                            .with_property($crate::__doubts_check!(@property_name $($property)+))
+                            .with_operator("~=")
+                            .with_expected(format!("{} within {}", $crate::render::debug(&expected), tolerance))
+                            .with_actual($crate::render::debug(actual))
+                            .with_reason(difference);
+                        Err(Box::new(if $negated { failure.negated() } else { failure }))
+                    }
+                }
+            }
+        }
+    };
+    (@exactly $e: expr, [$($expected:tt)+] in any order) => {
+        match (&$e, &$($expected)+) {
+            (subject, expected) => $crate::collections::contains_exactly(stringify!($e), subject,
+                $crate::__doubts_check!(@elements subject), $crate::__doubts_check!(@elements expected), true)
+        }
+    };
+    (@exactly $e: expr, [$($expected:tt)+] in order) => { $crate::__doubts_check!(@exactly $e, [$($expected)+]) };
+    (@exactly $e: expr, [$($expected:tt)+]) => {
+        match (&$e, &$($expected)+) {
+            (subject, expected) => $crate::collections::contains_exactly(stringify!($e), subject,
+                $crate::__doubts_check!(@elements subject), $crate::__doubts_check!(@elements expected), false)
+        }
+    };
+    (@exactly $e: expr, [$($expected:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::__doubts_check!(@exactly $e, [$($expected)* $next] $($rest)*)
+    };
+    (@elements $collection:ident) => {{
+        #[allow(unused_imports)]
+        use $crate::collections::{Borrowed as _, Cloned as _};
+        (&$crate::collections::Elements($collection)).elements()
+    }};
+    (@quantified $e: expr, $quantifier:ident, $i:ident) => {
+        match &$e {
+            subject => $crate::collections::quantified($crate::collections::Quantifier::$quantifier, stringify!($e), subject,
+                $crate::__doubts_check!(@elements subject),
+                $crate::naming::verb(stringify!($i)),
+                |element| element.$i())
+        }
+    };
+    (@quantified $e: expr, $quantifier:ident, $tt:tt $n: expr) => {
+        match (&$e, &$n) {
+            (subject, expected) => $crate::collections::quantified($crate::collections::Quantifier::$quantifier, stringify!($e), subject,
+                $crate::__doubts_check!(@elements subject),
+                format!("be {} {}", stringify!($tt), $crate::render::debug(expected)),
+                |element| **element $tt *expected)
+        }
+    };
+    (@verdict $e: expr, $verdict: expr, $actual: expr, $arguments: expr, $negated: expr, $failure: expr) => {
+        match $crate::verdict::Verdict::verdict($verdict, stringify!($e), $actual, $arguments, $negated) {
+            Ok(()) => Ok(()),
+            Err(Some(failure)) => Err(failure),
+            Err(None) => Err(Box::new(if $negated { $failure.negated() } else { $failure })),
+        }
+    };
+    (@text $e: expr, $relation:ident, $negated: expr, $expected: expr) => {
+        match (&$e, &$expected) {
+            (subject, expected) => $crate::strings::check($crate::strings::Relation::$relation, stringify!($e), subject, expected, $negated)
+        }
+    };
+    (@is $e: expr, $i:ident, $verb: expr) => {
+        match &$e {
+            subject => if subject.$i() {
+                Ok(())
+            } else {
+                #[allow(unused_imports)]
+                use $crate::outcome::{Stated as _, Unstated as _};
+                Err(Box::new(match (&$crate::outcome::Checked(subject)).state() {
+                    Some(state) => $crate::Failure::new(stringify!($e), $crate::render::debug(subject), $verb).with_reason(state),
+                    None => $crate::__doubts_check!(@predicate $e, subject, $i),
+                }))
+            }
+        }
+    };
+    (@then $e: expr, $variant:ident, $verb: expr, $($expectation:tt)+) => {
+        match $e {
+            subject => {
+                let checked = match &subject {
+                    $variant(value) => $crate::__doubts_check!(@chained value, $($expectation)+).map(|_| ()).map_err(|failure| {
+                        let failure_then = $crate::Failure::new(stringify!($e), $crate::render::debug(&subject), format!("{} then {}", $verb, failure.expectation()));
+                        Box::new(match failure.explanation() {
+                            Some(explanation) => failure_then.with_reason(explanation),
+                            None => failure_then,
+                        })
+                    }),
+                    _ => Err(Box::new($crate::Failure::new(stringify!($e), $crate::render::debug(&subject), $verb)
+                        .with_reason($crate::outcome::Outcome::state(&subject)))),
+                };
+                checked.map(|()| match subject {
+                    $variant(value) => value,
+                    _ => unreachable!(),
+                })
+            }
+        }
+    };
+    // the forms taking the value check the borrowed inner value, since it cannot be moved out of the tested value
+    (@chained $value:ident, is_ok then $($expectation:tt)+) => { $crate::__doubts_check!($value, is_ok then $($expectation)+) };
+    (@chained $value:ident, is_err then $($expectation:tt)+) => { $crate::__doubts_check!($value, is_err then $($expectation)+) };
+    (@chained $value:ident, is_some then $($expectation:tt)+) => { $crate::__doubts_check!($value, is_some then $($expectation)+) };
+    (@chained $value:ident, is_ok_with $expected: expr) => { $crate::__doubts_check!($value, is_ok_with $expected) };
+    (@chained $value:ident, is_some_and $predicate: expr) => { $crate::__doubts_check!($value, is_some_and $predicate) };
+    (@chained $value:ident, is_err_matching $pattern: pat $(if $guard: expr)?) => {
+        $crate::__doubts_check!($value, is_err_matching $pattern $(if $guard)?)
+    };
+    (@chained $value:ident, matches $($pattern: pat)|+ $(if $guard: expr)? => $result: expr) => {
+        $crate::__doubts_check!($value, matches $($pattern)|+ $(if $guard)? => $result)
+    };
+    (@chained $value:ident, $($expectation:tt)+) => { $crate::__doubts_check!(*$value, $($expectation)+) };
+    (@predicate $e: expr, $subject:ident, $i:ident) => {
+        match ($crate::__doubts_check!(@described $subject, $i), $crate::naming::property(stringify!($i))) {
+            (Some(description), _) => $crate::Failure::new(stringify!($e), $crate::render::debug($subject), description),
+            (None, Some(property)) => $crate::Failure::new(stringify!($e), $crate::render::debug($subject), "be").with_property(property),
+            (None, None) => $crate::Failure::new(stringify!($e), $crate::render::debug($subject), $crate::naming::verb(stringify!($i))),
+        }
+    };
+    (@verb $subject:ident, $i:ident) => {
+        $crate::__doubts_check!(@described $subject, $i).unwrap_or_else(|| $crate::naming::verb(stringify!($i)))
+    };
+    (@described $subject:ident, $i:ident) => {{
+        #[allow(unused_imports)]
+        use $crate::naming::{Described as _, Undescribed as _};
+        (&$crate::naming::Subject($subject)).description(stringify!($i))
+    }};
+    (@shown $value: expr) => {{
+        #[allow(unused_imports)]
+        use $crate::render::{Debugged as _, Displayed as _};
+        (&$crate::render::Shown($value)).rendering()
+    }};
+    (@property $subject:ident, $i:ident) => { $subject.$i() };
+    (@property $subject:ident, . $($field:ident).+) => { $subject.$($field).+ };
+    (@property $subject:ident, . $($path:tt)+) => { $subject.$($path)+ };
+    (@property $subject:ident, $($field:ident).+) => { $subject.$($field).+ };
+    (@property $subject:ident, $($path:tt)+) => { $subject.$($path)+ };
+    (@property_name . $($field:tt)+) => { $crate::naming::path(stringify!($($field)+)) };
+    (@property_name $($field:tt)+) => { $crate::naming::path(stringify!($($field)+)) };
+    (@call $e: expr, $negated: expr, $i:ident, $($n: expr),+) => {
+        match &$e {
+            subject => $crate::__doubts_check!(@arguments $e, $negated, subject.$i [] $($n),+)
+        }
+    };
+    // each argument is evaluated once, in its own binding, and printed before being given to the method
+    (@arguments $e: expr, $negated: expr, $subject:ident.$i:ident [$($argument:ident)*] $n: expr $(, $rest: expr)*) => {
+        match $n {
+            argument => $crate::__doubts_check!(@arguments $e, $negated, $subject.$i [$($argument)* argument] $($rest),*)
+        }
+    };
+    (@arguments $e: expr, $negated: expr, $subject:ident.$i:ident [$($argument:ident)+]) => {{
+        let expected = [$($crate::__doubts_check!(@shown &$argument)),+].join(",");
+        if $subject.$i($($argument),+) != $negated {
+            Ok(())
+        } else {
+            let failure = $crate::Failure::new(stringify!($e), $crate::render::debug($subject), $crate::__doubts_check!(@verb $subject, $i))
+                .with_expected(expected);
+            Err(Box::new(if $negated { failure.negated() } else { failure }))
+        }
+    }};
+    ($e: expr, satisfies $matcher: expr) => {
+        match (&$e, &$matcher) {
+            (subject, matcher) => {
+                #[allow(unused_imports)]
+                use $crate::matchers::{Affirmed as _, DoublyNegated as _, Negated as _};
+                (&&$crate::matchers::Satisfied(matcher)).check(stringify!($e), subject)
+            }
+        }
+    };
+    ($e: expr, contains_all $expected: expr) => {
+        match (&$e, &$expected) {
+            (subject, expected) => $crate::collections::contains_all(stringify!($e), subject,
+                $crate::__doubts_check!(@elements subject), $crate::__doubts_check!(@elements expected))
+        }
+    };
+    ($e: expr, contains_exactly $($expected:tt)+) => { $crate::__doubts_check!(@exactly $e, [] $($expected)+) };
+    ($e: expr, each $($expectation:tt)+) => {
+        match &$e {
+            subject => {
+                let failures = $crate::__doubts_check!(@elements subject).enumerate()
+                    .filter_map(|(index, element)| $crate::__doubts_check!(@chained element, $($expectation)+).err().map(|failure| (index, failure)))
+                    .collect();
+                $crate::collections::each(stringify!($e), subject, failures)
+            }
+        }
+    };
+    ($e: expr, any $($predicate:tt)+) => { $crate::__doubts_check!(@quantified $e, Any, $($predicate)+) };
+    ($e: expr, none $($predicate:tt)+) => { $crate::__doubts_check!(@quantified $e, None, $($predicate)+) };
+    ($e: expr, is_sorted) => {
+        match &$e {
+            subject => {
+                #[allow(unused_imports)]
+                use $crate::collections::{ClonedSortable as _, Sortable as _};
+                $crate::__doubts_check!(@verdict $e, subject.is_sorted(), subject, "", false,
+                    $crate::__doubts_check!(@predicate $e, subject, is_sorted))
+            }
+        }
+    };
+    ($e: expr, is_sorted_by_key $key: expr) => {
+        match &$e {
+            subject => {
+                #[allow(unused_imports)]
+                use $crate::collections::{ClonedSortable as _, Sortable as _};
+                $crate::__doubts_check!(@verdict $e, subject.is_sorted_by_key($key), subject, stringify!($key), false,
+                    $crate::Failure::new(stringify!($e), $crate::render::debug(subject), $crate::__doubts_check!(@verb subject, is_sorted_by_key))
+                        .with_expected(stringify!($key)))
+            }
+        }
+    };
+    ($e: expr, has_key $key: expr) => { $crate::__doubts_check!(@has_key $e, false, $key) };
+    ($e: expr, not has_key $key: expr) => { $crate::__doubts_check!(@has_key $e, true, $key) };
+    (@map $map:ident) => {{
+        #[allow(unused_imports)]
+        use $crate::maps::{KeysOrdered as _, KeysUnordered as _};
+        (&$crate::maps::Map($map)).view()
+    }};
+    (@has_key $e: expr, $negated: expr, $key: expr) => {
+        match (&$e, $key) {
+            (subject, key) => {
+                // the key is given to the method, as written
+                let expected = $crate::render::debug(&key);
+                #[allow(unused_imports)]
+                use $crate::maps::HasKey as _;
+                $crate::__doubts_check!(@verdict $e, subject.has_key(key), &$crate::__doubts_check!(@map subject), "", $negated,
+                    $crate::Failure::new(stringify!($e), $crate::render::debug(subject), $crate::__doubts_check!(@verb subject, has_key))
+                        .with_expected(expected))
+            }
+        }
+    };
+    ($e: expr, has_entry $key: expr => $value: expr) => {
+        match (&$e, &$key, &$value) {
+            (subject, key, value) => $crate::maps::has_entry(stringify!($e), &$crate::__doubts_check!(@map subject), key, value)
+        }
+    };
+    ($e: expr, has_keys exactly $keys: expr) => {
+        match &$e {
+            subject => $crate::maps::has_keys(stringify!($e), &$crate::__doubts_check!(@map subject), $keys, true)
+        }
+    };
+    ($e: expr, has_keys $keys: expr) => {
+        match &$e {
+            subject => $crate::maps::has_keys(stringify!($e), &$crate::__doubts_check!(@map subject), $keys, false)
+        }
+    };
+    ($e: expr, has_entries exactly $expected: expr) => {
+        match (&$e, &$expected) {
+            (subject, expected) => $crate::maps::has_entries(stringify!($e), &$crate::__doubts_check!(@map subject),
+                &$crate::__doubts_check!(@map expected))
+        }
+    };
+    ($e: expr, is_ok) => { $crate::__doubts_check!(@is $e, is_ok, "be ok") };
+    ($e: expr, is_err) => { $crate::__doubts_check!(@is $e, is_err, "be an error") };
+    ($e: expr, is_some) => { $crate::__doubts_check!(@is $e, is_some, "be some") };
+    ($e: expr, is_none) => { $crate::__doubts_check!(@is $e, is_none, "be none") };
+    ($e: expr, is_ok then $($expectation:tt)+) => { $crate::__doubts_check!(@then $e, Ok, "be ok", $($expectation)+) };
+    ($e: expr, is_err then $($expectation:tt)+) => { $crate::__doubts_check!(@then $e, Err, "be an error", $($expectation)+) };
+    ($e: expr, is_some then $($expectation:tt)+) => { $crate::__doubts_check!(@then $e, Some, "be some", $($expectation)+) };
+    ($e: expr, is_ok_with $expected: expr) => {
+        match ($e, &$expected) {
+            (subject, expected) => {
+                let checked = match &subject {
+                    Ok(value) if *value == *expected => Ok(()),
+                    _ => Err(Box::new($crate::Failure::new(stringify!($e), $crate::render::debug(&subject), "be ok with")
+                        .with_expected($crate::render::debug(expected))
+                        .with_reason($crate::outcome::Outcome::state(&subject)))),
+                };
+                checked.map(|()| match subject {
+                    Ok(value) => value,
+                    _ => unreachable!(),
+                })
+            }
+        }
+    };
+    ($e: expr, is_some_and $predicate: expr) => {
+        match ($e, $predicate) {
+            (subject, predicate) => {
+                let checked = match &subject {
+                    Some(value) if predicate(value) => Ok(()),
+                    _ => Err(Box::new($crate::Failure::new(stringify!($e), $crate::render::debug(&subject), "be some and satisfy")
+                        .with_expected(stringify!($predicate))
+                        .with_reason($crate::outcome::Outcome::state(&subject)))),
+                };
+                checked.map(|()| match subject {
+                    Some(value) => value,
+                    _ => unreachable!(),
+                })
+            }
+        }
+    };
+    ($e: expr, is_err_matching $pattern: pat $(if $guard: expr)?) => {
+        match $e {
+            subject => {
+                let checked = match &subject {
+                    Err($pattern) $(if $guard)? => Ok(()),
+                    _ => Err(Box::new($crate::Failure::new(stringify!($e), $crate::render::debug(&subject), "be an error matching")
+                        .with_expected(stringify!($pattern $(if $guard)?))
+                        .with_reason($crate::outcome::Outcome::state(&subject)))),
+                };
+                checked.map(|()| match subject {
+                    Err(error) => error,
+                    _ => unreachable!(),
+                })
+            }
+        }
+    };
+    ($e: expr, panics) => { $crate::panics::panics(stringify!($e), $e, $crate::panics::Expectation::Any) };
+    ($e: expr, panics with $expected: expr) => {
+        match &$expected {
+            expected => $crate::panics::panics(stringify!($e), $e, $crate::panics::Expectation::With(expected.as_ref()))
+        }
+    };
+    ($e: expr, panics matching $pattern: expr) => { $crate::__doubts_regex!(@panics $e, $pattern) };
+    ($e: expr, does not panic) => { $crate::panics::returns(stringify!($e), $e) };
+    ($e: expr, starts with $expected: expr) => { $crate::__doubts_check!(@text $e, Prefix, false, $expected) };
+    ($e: expr, not starts with $expected: expr) => { $crate::__doubts_check!(@text $e, Prefix, true, $expected) };
+    ($e: expr, ends with $expected: expr) => { $crate::__doubts_check!(@text $e, Suffix, false, $expected) };
+    ($e: expr, not ends with $expected: expr) => { $crate::__doubts_check!(@text $e, Suffix, true, $expected) };
+    ($e: expr, contains substring $expected: expr) => { $crate::__doubts_check!(@text $e, Substring, false, $expected) };
+    ($e: expr, not contains substring $expected: expr) => { $crate::__doubts_check!(@text $e, Substring, true, $expected) };
+    ($e: expr, equals ignoring case $expected: expr) => { $crate::__doubts_check!(@text $e, IgnoringCase, false, $expected) };
+    ($e: expr, not equals ignoring case $expected: expr) => { $crate::__doubts_check!(@text $e, IgnoringCase, true, $expected) };
+    ($e: expr, matches snapshot $name: expr) => {
+        match (&$e, &$name) {
+            (subject, name) => $crate::snapshots::check(stringify!($e), subject, env!("CARGO_MANIFEST_DIR"), file!(), name.as_ref())
+        }
+    };
+    ($e: expr, matches regex $pattern: expr) => { $crate::__doubts_regex!(@matches $e, false, $pattern) };
+    ($e: expr, not matches regex $pattern: expr) => { $crate::__doubts_regex!(@matches $e, true, $pattern) };
+    ($e: expr, matches $($pattern: pat)|+ $(if $guard: expr)? => $result: expr) => {
+        match $e {
+            #[allow(unreachable_patterns)]
+            subject => match subject {
+                $($pattern)|+ $(if $guard)? => Ok($result),
+                _ => Err(Box::new($crate::Failure::new(stringify!($e), $crate::render::debug(&subject), "match")
+                    .with_expected(stringify!($($pattern)|+ $(if $guard)?)))),
+            }
+        }
+    };
+    ($e: expr, matches $($pattern: pat)|+ $(if $guard: expr)?) => {
+        match &$e {
+            #[allow(unreachable_patterns)]
+            subject => match subject {
+                $($pattern)|+ $(if $guard)? => Ok(()),
+                _ => Err(Box::new($crate::Failure::new(stringify!($e), $crate::render::debug(subject), "match")
+                    .with_expected(stringify!($($pattern)|+ $(if $guard)?)))),
+            }
+        }
+    };
+    ($e: expr, not matches $($pattern: pat)|+ $(if $guard: expr)?) => {
+        match &$e {
+            #[allow(unreachable_patterns)]
+            subject => match subject {
+                $($pattern)|+ $(if $guard)? => Err(Box::new($crate::Failure::new(stringify!($e), $crate::render::debug(subject), "match").negated()
+                    .with_expected(stringify!($($pattern)|+ $(if $guard)?)))),
+                _ => Ok(()),
+            }
+        }
+    };
+    ($e: expr, is close to $($expected:tt)+) => {
+        $crate::__doubts_check!(@close $e, [] [] [] $($expected)+)
+    };
+    ($e: expr, is not close to $($expected:tt)+) => {
+        $crate::__doubts_check!(@close $e, [not] [] [] $($expected)+)
+    };
+    ($e: expr, has same elements as $expected: expr) => {
+        match (&$e, &$expected) {
+            (subject, expected) => $crate::collections::same_elements(stringify!($e), subject,
+                $crate::__doubts_check!(@elements subject), $crate::__doubts_check!(@elements expected), false)
+        }
+    };
+    ($e: expr, has not same elements as $expected: expr) => {
+        match (&$e, &$expected) {
+            (subject, expected) => $crate::collections::same_elements(stringify!($e), subject,
+                $crate::__doubts_check!(@elements subject), $crate::__doubts_check!(@elements expected), true)
+        }
+    };
+    ($e: expr, has not $($property:tt)+) => {
+        $crate::__doubts_check!(@has $e, [not] [] $($property)+)
+    };
+    ($e: expr, has $($property:tt)+) => {
+        $crate::__doubts_check!(@has $e, [] [] $($property)+)
+    };
+    ($e: expr, == $n: expr) => { $crate::__doubts_check!(@compare $e, == $n) };
+    ($e: expr, != $n: expr) => { $crate::__doubts_check!(@compare $e, != $n) };
+    ($e: expr, < $n: expr) => { $crate::__doubts_check!(@compare $e, < $n) };
+    ($e: expr, <= $n: expr) => { $crate::__doubts_check!(@compare $e, <= $n) };
+    ($e: expr, > $n: expr) => { $crate::__doubts_check!(@compare $e, > $n) };
+    ($e: expr, >= $n: expr) => { $crate::__doubts_check!(@compare $e, >= $n) };
+    ($e: expr, not $i:ident) => {
+        match &$e {
+            subject => if !subject.$i() {
+                Ok(())
+            } else {
+                Err(Box::new($crate::__doubts_check!(@predicate $e, subject, $i).negated()))
+            }
+        }
+    };
+    ($e: expr, not $i:ident $($n: expr),+) => { $crate::__doubts_check!(@call $e, true, $i, $($n),+) };
+    ($e: expr, $i:ident) => {
+        match &$e {
+            subject => if subject.$i() {
+                Ok(())
+            } else {
+                Err(Box::new($crate::__doubts_check!(@predicate $e, subject, $i)))
             }
         }
     };
-}
\ No newline at end of file
+    ($e: expr, $i:ident $($n: expr),+) => { $crate::__doubts_check!(@call $e, false, $i, $($n),+) };
+}
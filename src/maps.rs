@@ -0,0 +1,251 @@
+//! Checks on maps, whose messages list their entries in the order of the map, or sorted by key when it has none.
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{self, Debug};
+
+use crate::render;
+use crate::verdict::Verdict;
+use crate::Failure;
+
+/// The maps that can be checked with `has_key`, `has_entry`, `has_keys` and `has_entries exactly`.
+///
+/// It is implemented for `HashMap` and `BTreeMap`, and can be implemented for other maps:
+/// ```
+/// # #[macro_use] extern crate doubts;
+/// use doubts::MapLike;
+///
+/// #[derive(Debug)]
+/// struct Headers(Vec<(String, String)>);
+///
+/// impl MapLike for Headers {
+///     type Key = String;
+///     type Value = String;
+///
+///     fn entries(&self) -> Vec<(&String, &String)> {
+///         self.0.iter().map(|(name, value)| (name, value)).collect()
+///     }
+/// }
+///
+/// # fn main() {
+/// let headers = Headers(vec![("Host".to_string(), "example.com".to_string())]);
+/// assert_that!(headers, has_entry "Host" => "example.com");
+/// # }
+/// ```
+pub trait MapLike {
+    type Key: Debug;
+    type Value: Debug;
+
+    /// The entries of the map, in its order when it has one, like the key order of a `BTreeMap`.
+    fn entries(&self) -> Vec<(&Self::Key, &Self::Value)>;
+
+    /// Whether the entries come in an order of the map. The entries of the other maps, like `HashMap`s,
+    /// are sorted by key in messages, by their `Debug` representation when the keys cannot be compared.
+    fn ordered(&self) -> bool {
+        true
+    }
+}
+
+impl<K: Debug, V: Debug, S> MapLike for HashMap<K, V, S> {
+    type Key = K;
+    type Value = V;
+
+    fn entries(&self) -> Vec<(&K, &V)> {
+        self.iter().collect()
+    }
+
+    fn ordered(&self) -> bool {
+        false
+    }
+}
+
+impl<K: Debug, V: Debug> MapLike for BTreeMap<K, V> {
+    type Key = K;
+    type Value = V;
+
+    fn entries(&self) -> Vec<(&K, &V)> {
+        self.iter().collect()
+    }
+}
+
+impl<M: MapLike + ?Sized> MapLike for &M {
+    type Key = M::Key;
+    type Value = M::Value;
+
+    fn entries(&self) -> Vec<(&M::Key, &M::Value)> {
+        (**self).entries()
+    }
+
+    fn ordered(&self) -> bool {
+        (**self).ordered()
+    }
+}
+
+/// A checked map, whose entries are sorted by key when it has no order and its keys can be compared,
+/// dispatched as described on `__doubts_check!`.
+pub struct Map<'a, M: ?Sized>(pub &'a M);
+
+/// The maps whose keys can be compared, seen with their entries in key order.
+pub trait KeysOrdered<'a, M: ?Sized> {
+    fn view(&self) -> ByKey<'a, M>;
+}
+
+impl<'a, M> KeysOrdered<'a, M> for Map<'a, M> where M: MapLike + ?Sized, M::Key: Ord {
+    fn view(&self) -> ByKey<'a, M> {
+        ByKey(self.0)
+    }
+}
+
+/// The fallback for the maps whose keys cannot be compared, seen as they are.
+pub trait KeysUnordered<'a, M: ?Sized> {
+    fn view(&self) -> &'a M;
+}
+
+impl<'a, M: ?Sized> KeysUnordered<'a, M> for &Map<'a, M> {
+    fn view(&self) -> &'a M {
+        self.0
+    }
+}
+
+/// A map whose keys can be compared, with its entries in key order when it has no order.
+pub struct ByKey<'a, M: ?Sized>(&'a M);
+
+impl<M> MapLike for ByKey<'_, M> where M: MapLike + ?Sized, M::Key: Ord {
+    type Key = M::Key;
+    type Value = M::Value;
+
+    fn entries(&self) -> Vec<(&M::Key, &M::Value)> {
+        let mut entries = self.0.entries();
+        if !self.0.ordered() {
+            entries.sort_by_key(|(key, _)| *key);
+        }
+        entries
+    }
+}
+
+/// A map, shown with its entries in order.
+struct Sorted<'a, M: ?Sized>(&'a M);
+
+impl<M: MapLike + ?Sized> Debug for Sorted<'_, M> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(in_order(self.0)).finish()
+    }
+}
+
+/// Checks that the map has the key, or not when `negated`.
+pub fn has_key<M, Q>(subject: &str, map: &M, key: &Q, negated: bool) -> Result<(), Box<Failure>>
+    where M: MapLike + ?Sized, M::Key: PartialEq<Q>, Q: Debug + ?Sized {
+    if map.entries().iter().any(|(k, _)| *k == key) != negated {
+        return Ok(());
+    }
+    let failure = Failure::new(subject, render::debug(&Sorted(map)), "have")
+        .with_property("key")
+        .with_expected(render::debug(key));
+    Err(Box::new(if negated { failure.negated() } else { failure }))
+}
+
+/// The `has_key` check of the maps, named like the method so that the values having their own `has_key` method keep it.
+pub trait HasKey {
+    fn has_key<Q>(&self, key: Q) -> Lookup<Q>;
+}
+
+impl<M: MapLike + ?Sized> HasKey for M {
+    fn has_key<Q>(&self, key: Q) -> Lookup<Q> {
+        Lookup(key)
+    }
+}
+
+/// A key to look up in a map.
+pub struct Lookup<Q>(Q);
+
+impl<M, Q> Verdict<M> for Lookup<Q> where M: MapLike + ?Sized, M::Key: PartialEq<Q>, Q: Debug {
+    fn verdict(self, subject: &str, actual: &M, _arguments: &str, negated: bool) -> Result<(), Option<Box<Failure>>> {
+        has_key(subject, actual, &self.0, negated).map_err(Some)
+    }
+}
+
+/// Checks that the map has the key, with the value.
+pub fn has_entry<M, Q, W>(subject: &str, map: &M, key: &Q, value: &W) -> Result<(), Box<Failure>>
+    where M: MapLike + ?Sized, M::Key: PartialEq<Q>, M::Value: PartialEq<W>, Q: Debug + ?Sized, W: Debug + ?Sized {
+    let entries = map.entries();
+    let reason = match entries.iter().find(|(k, _)| *k == key) {
+        Some((_, v)) if *v == value => return Ok(()),
+        Some((k, v)) => format!("it has {}", entry(k, v)),
+        None => format!("it has no key {}", render::debug(key)),
+    };
+    Err(Box::new(Failure::new(subject, render::debug(&Sorted(map)), "have")
+        .with_property("entry")
+        .with_expected(entry(key, value))
+        .with_reason(reason)))
+}
+
+/// Checks that the map has all the keys, and only them when `exactly`.
+pub fn has_keys<M, Q>(subject: &str, map: &M, keys: impl IntoIterator<Item = Q>, exactly: bool) -> Result<(), Box<Failure>>
+    where M: MapLike + ?Sized, M::Key: PartialEq<Q>, Q: Debug {
+    let entries = map.entries();
+    let keys: Vec<Q> = keys.into_iter().collect();
+    let missing: Vec<&Q> = keys.iter().filter(|key| !entries.iter().any(|(k, _)| *k == *key)).collect();
+    let unexpected: Vec<&M::Key> = if exactly {
+        in_order(map).into_iter().filter(|(k, _)| !keys.iter().any(|key| *k == key)).map(|(k, _)| k).collect()
+    } else {
+        Vec::new()
+    };
+    let mut differences = Vec::new();
+    if !missing.is_empty() {
+        differences.push(format!("missing {}", listed(&missing)));
+    }
+    if !unexpected.is_empty() {
+        differences.push(format!("unexpected {}", listed(&unexpected)));
+    }
+    if differences.is_empty() {
+        return Ok(());
+    }
+    Err(Box::new(Failure::new(subject, render::debug(&Sorted(map)), if exactly { "have exactly the keys" } else { "have all of the keys" })
+        .with_expected(render::debug(&keys))
+        .with_reason(differences.join("; "))))
+}
+
+/// Checks that the map has exactly the expected entries, telling the missing, unexpected and different ones.
+pub fn has_entries<M, N>(subject: &str, map: &M, expected: &N) -> Result<(), Box<Failure>>
+    where M: MapLike + ?Sized, N: MapLike + ?Sized, M::Key: PartialEq<N::Key>, M::Value: PartialEq<N::Value> {
+    let (entries, expected_entries) = (map.entries(), expected.entries());
+    let (mut missing, mut different) = (Vec::new(), Vec::new());
+    for (key, value) in in_order(expected) {
+        match entries.iter().find(|(k, _)| *k == key) {
+            None => missing.push(entry(key, value)),
+            Some((k, v)) if *v != value => different.push(format!("{} instead of {}", entry(k, v), render::debug(value))),
+            Some(_) => {}
+        }
+    }
+    let unexpected: Vec<String> = in_order(map)
+        .into_iter()
+        .filter(|(k, _)| !expected_entries.iter().any(|(key, _)| *k == *key))
+        .map(|(k, v)| entry(k, v))
+        .collect();
+    let differences: Vec<String> = [("missing ", missing), ("unexpected ", unexpected), ("", different)].iter()
+        .filter(|(_, entries)| !entries.is_empty())
+        .map(|(label, entries)| format!("{}{}", label, entries.join(", ")))
+        .collect();
+    if differences.is_empty() {
+        return Ok(());
+    }
+    Err(Box::new(Failure::new(subject, render::debug(&Sorted(map)), "have exactly the entries")
+        .with_expected(render::debug(&Sorted(expected)))
+        .with_reason(differences.join("; "))))
+}
+
+/// The entries of the map, in its order, or sorted by the `Debug` representation of their keys when it has none,
+/// so that messages do not depend on the arbitrary order of a map.
+fn in_order<M: MapLike + ?Sized>(map: &M) -> Vec<(&M::Key, &M::Value)> {
+    let mut entries = map.entries();
+    if !map.ordered() {
+        entries.sort_by_cached_key(|(key, _)| format!("{:?}", key));
+    }
+    entries
+}
+
+fn entry<K: Debug + ?Sized, V: Debug + ?Sized>(key: &K, value: &V) -> String {
+    format!("{} => {}", render::debug(key), render::debug(value))
+}
+
+fn listed<X: Debug>(elements: &[X]) -> String {
+    elements.iter().map(render::debug).collect::<Vec<_>>().join(", ")
+}
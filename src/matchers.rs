@@ -0,0 +1,303 @@
+//! Reusable expectations, to use with `assert_that!(value, satisfies matcher)`.
+//!
+//! ```should_panic
+//! # #[macro_use] extern crate doubts;
+//! use doubts::matchers::{all_of, contains, gt, lt, not};
+//!
+//! # fn main() {
+//! let x = 3;
+//! assert_that!(x, satisfies all_of((gt(0), lt(10))));
+//! let v = vec![1, 2];
+//! assert_that!(v, satisfies not(contains(3)));
+//! assert_that!(x, satisfies all_of((gt(0), gt(5))))
+//! # }
+//! ```
+//! Would fail with a message like ``Expected `x`=3 to be greater than 0 and be greater than 5, but it fails to be greater than 5.``
+use std::fmt::Debug;
+
+use crate::Failure;
+
+/// An expectation about values of type `T`.
+///
+/// Implement it to write your own nameable expectations: the failure messages are built from
+/// its description, like ``Expected `x`=3 to be greater than 5.``
+pub trait Matcher<T: ?Sized> {
+    /// Whether the actual value meets the expectation.
+    fn matches(&self, actual: &T) -> bool;
+
+    /// The expectation, as a verb phrase following "to", like `be greater than 5` or `contain 2`.
+    fn describe(&self) -> String;
+
+    /// Why the actual value does not meet the expectation, when its `Debug` representation does not tell it alone.
+    fn describe_mismatch(&self, actual: &T) -> Option<String> {
+        let _ = actual;
+        None
+    }
+}
+
+impl<T: ?Sized, M: Matcher<T> + ?Sized> Matcher<T> for &M {
+    fn matches(&self, actual: &T) -> bool {
+        (**self).matches(actual)
+    }
+
+    fn describe(&self) -> String {
+        (**self).describe()
+    }
+
+    fn describe_mismatch(&self, actual: &T) -> Option<String> {
+        (**self).describe_mismatch(actual)
+    }
+}
+
+impl<T: ?Sized, M: Matcher<T> + ?Sized> Matcher<T> for Box<M> {
+    fn matches(&self, actual: &T) -> bool {
+        (**self).matches(actual)
+    }
+
+    fn describe(&self) -> String {
+        (**self).describe()
+    }
+
+    fn describe_mismatch(&self, actual: &T) -> Option<String> {
+        (**self).describe_mismatch(actual)
+    }
+}
+
+#[doc(hidden)]
+pub fn check<T: Debug + ?Sized, M: Matcher<T> + ?Sized>(subject: &str, actual: &T, matcher: &M) -> Result<(), Box<Failure>> {
+    if matcher.matches(actual) {
+        return Ok(());
+    }
+    let failure = Failure::new(subject, crate::render::debug(actual), matcher.describe());
+    Err(Box::new(match matcher.describe_mismatch(actual) {
+        Some(mismatch) => failure.with_reason(mismatch),
+        None => failure,
+    }))
+}
+
+/// A matcher checked with `satisfies`, whose negations are worded like the other negated expectations,
+/// dispatched as described on `__doubts_check!`.
+#[doc(hidden)]
+pub struct Satisfied<'a, M: ?Sized>(pub &'a M);
+
+/// The negations of negations, checked as the matcher they negate twice.
+#[doc(hidden)]
+pub trait DoublyNegated<T: ?Sized> {
+    fn check(&self, subject: &str, actual: &T) -> Result<(), Box<Failure>>;
+}
+
+impl<T: Debug + ?Sized, M: Matcher<T>> DoublyNegated<T> for &Satisfied<'_, Not<Not<M>>> {
+    fn check(&self, subject: &str, actual: &T) -> Result<(), Box<Failure>> {
+        let Not(Not(matcher)) = self.0;
+        check(subject, actual, matcher)
+    }
+}
+
+/// The negations, failing with ``Expected `x`=3 not to be equal to 3.``
+#[doc(hidden)]
+pub trait Negated<T: ?Sized> {
+    fn check(&self, subject: &str, actual: &T) -> Result<(), Box<Failure>>;
+}
+
+impl<T: Debug + ?Sized, M: Matcher<T>> Negated<T> for &&Satisfied<'_, Not<M>> {
+    fn check(&self, subject: &str, actual: &T) -> Result<(), Box<Failure>> {
+        let Not(negated) = self.0;
+        if !negated.matches(actual) {
+            return Ok(());
+        }
+        Err(Box::new(Failure::new(subject, crate::render::debug(actual), negated.describe()).negated()))
+    }
+}
+
+/// The other matchers, reached last.
+#[doc(hidden)]
+pub trait Affirmed<T: ?Sized> {
+    fn check(&self, subject: &str, actual: &T) -> Result<(), Box<Failure>>;
+}
+
+impl<T: Debug + ?Sized, M: Matcher<T> + ?Sized> Affirmed<T> for Satisfied<'_, M> {
+    fn check(&self, subject: &str, actual: &T) -> Result<(), Box<Failure>> {
+        check(subject, actual, self.0)
+    }
+}
+
+macro_rules! comparison {
+    ($(#[$doc:meta])* $function:ident, $matcher:ident, $bound:ident, $operator:tt, $description:expr) => {
+        $(#[$doc])*
+        pub fn $function<E>(expected: E) -> $matcher<E> {
+            $matcher(expected)
+        }
+
+        #[derive(Debug, Clone)]
+        pub struct $matcher<E>(E);
+
+        impl<T: $bound<E> + ?Sized, E: Debug> Matcher<T> for $matcher<E> {
+            fn matches(&self, actual: &T) -> bool {
+                *actual $operator self.0
+            }
+
+            fn describe(&self) -> String {
+                format!("{} {:?}", $description, self.0)
+            }
+        }
+    };
+}
+
+comparison!(
+    /// Matches values equal to `expected`.
+    eq, Eq, PartialEq, ==, "be equal to");
+comparison!(
+    /// Matches values not equal to `expected`.
+    ne, Ne, PartialEq, !=, "be different from");
+comparison!(
+    /// Matches values greater than `expected`.
+    gt, Gt, PartialOrd, >, "be greater than");
+comparison!(
+    /// Matches values greater than or equal to `expected`.
+    ge, Ge, PartialOrd, >=, "be greater than or equal to");
+comparison!(
+    /// Matches values less than `expected`.
+    lt, Lt, PartialOrd, <, "be less than");
+comparison!(
+    /// Matches values less than or equal to `expected`.
+    le, Le, PartialOrd, <=, "be less than or equal to");
+
+/// Matches slices, arrays and vectors containing an element equal to `element`.
+pub fn contains<E>(element: E) -> Contains<E> {
+    Contains(element)
+}
+
+#[derive(Debug, Clone)]
+pub struct Contains<E>(E);
+
+impl<X: PartialEq<E>, E: Debug> Matcher<[X]> for Contains<E> {
+    fn matches(&self, actual: &[X]) -> bool {
+        actual.iter().any(|element| *element == self.0)
+    }
+
+    fn describe(&self) -> String {
+        format!("contain {:?}", self.0)
+    }
+}
+
+impl<X: PartialEq<E>, E: Debug> Matcher<Vec<X>> for Contains<E> {
+    fn matches(&self, actual: &Vec<X>) -> bool {
+        Matcher::<[X]>::matches(self, actual)
+    }
+
+    fn describe(&self) -> String {
+        Matcher::<[X]>::describe(self)
+    }
+}
+
+impl<X: PartialEq<E>, E: Debug, const N: usize> Matcher<[X; N]> for Contains<E> {
+    fn matches(&self, actual: &[X; N]) -> bool {
+        Matcher::<[X]>::matches(self, actual)
+    }
+
+    fn describe(&self) -> String {
+        Matcher::<[X]>::describe(self)
+    }
+}
+
+/// Matches the values that `matcher` does not match.
+///
+/// Its failures are worded like the other negated expectations:
+/// ```
+/// # #[macro_use] extern crate doubts;
+/// use doubts::matchers::{eq, not};
+///
+/// # fn main() {
+/// let x = 3;
+/// let error = verify_that!(x, satisfies not(eq(3))).unwrap_err();
+/// assert_eq!(error.to_string(), "Expected `x`=3 not to be equal to 3.");
+/// let error = verify_that!(x, satisfies not(not(eq(4)))).unwrap_err();
+/// assert_eq!(error.to_string(), "Expected `x`=3 to be equal to 4.");
+/// # }
+/// ```
+pub fn not<M>(matcher: M) -> Not<M> {
+    Not(matcher)
+}
+
+#[derive(Debug, Clone)]
+pub struct Not<M>(M);
+
+impl<T: ?Sized, M: Matcher<T>> Matcher<T> for Not<M> {
+    fn matches(&self, actual: &T) -> bool {
+        !self.0.matches(actual)
+    }
+
+    fn describe(&self) -> String {
+        format!("not {}", self.0.describe())
+    }
+}
+
+/// Several matchers for the same type: a tuple of matchers, or a vector of boxed ones.
+pub trait Matchers<T: ?Sized> {
+    fn each(&self) -> Vec<&dyn Matcher<T>>;
+}
+
+impl<T: ?Sized> Matchers<T> for Vec<Box<dyn Matcher<T>>> {
+    fn each(&self) -> Vec<&dyn Matcher<T>> {
+        self.iter().map(|matcher| matcher as &dyn Matcher<T>).collect()
+    }
+}
+
+macro_rules! tuple_matchers {
+    ($($matcher:ident $index:tt),+) => {
+        impl<T: ?Sized, $($matcher: Matcher<T>),+> Matchers<T> for ($($matcher,)+) {
+            fn each(&self) -> Vec<&dyn Matcher<T>> {
+                vec![$(&self.$index as &dyn Matcher<T>),+]
+            }
+        }
+    };
+}
+
+tuple_matchers!(A 0, B 1);
+tuple_matchers!(A 0, B 1, C 2);
+tuple_matchers!(A 0, B 1, C 2, D 3);
+tuple_matchers!(A 0, B 1, C 2, D 3, E 4);
+tuple_matchers!(A 0, B 1, C 2, D 3, E 4, F 5);
+
+/// Matches the values that every one of `matchers` matches.
+pub fn all_of<M>(matchers: M) -> AllOf<M> {
+    AllOf(matchers)
+}
+
+#[derive(Debug, Clone)]
+pub struct AllOf<M>(M);
+
+impl<T: ?Sized, M: Matchers<T>> Matcher<T> for AllOf<M> {
+    fn matches(&self, actual: &T) -> bool {
+        self.0.each().iter().all(|matcher| matcher.matches(actual))
+    }
+
+    fn describe(&self) -> String {
+        self.0.each().iter().map(|matcher| matcher.describe()).collect::<Vec<_>>().join(" and ")
+    }
+
+    fn describe_mismatch(&self, actual: &T) -> Option<String> {
+        self.0.each().iter()
+            .find(|matcher| !matcher.matches(actual))
+            .map(|matcher| matcher.describe_mismatch(actual)
+                .unwrap_or_else(|| format!("it fails to {}", matcher.describe())))
+    }
+}
+
+/// Matches the values that at least one of `matchers` matches.
+pub fn any_of<M>(matchers: M) -> AnyOf<M> {
+    AnyOf(matchers)
+}
+
+#[derive(Debug, Clone)]
+pub struct AnyOf<M>(M);
+
+impl<T: ?Sized, M: Matchers<T>> Matcher<T> for AnyOf<M> {
+    fn matches(&self, actual: &T) -> bool {
+        self.0.each().iter().any(|matcher| matcher.matches(actual))
+    }
+
+    fn describe(&self) -> String {
+        self.0.each().iter().map(|matcher| matcher.describe()).collect::<Vec<_>>().join(" or ")
+    }
+}
@@ -0,0 +1,130 @@
+//! Rendering of method names in failure messages.
+
+/// Custom wording of the predicate methods of a type, used in messages instead of the method names.
+///
+/// The description is the predicate as it follows "to" in a sentence, so that negated messages read well too.
+/// ```should_panic
+/// # #[macro_use] extern crate doubts;
+/// use doubts::Describe;
+///
+/// #[derive(Debug)]
+/// struct Account(String);
+///
+/// impl Account {
+///     fn is_valid_iban(&self) -> bool {
+///         false
+///     }
+/// }
+///
+/// impl Describe for Account {
+///     fn describe(&self, method: &str) -> Option<String> {
+///         match method {
+///             "is_valid_iban" => Some("be a valid IBAN".to_string()),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// # fn main() {
+/// let account = Account("FR76".to_string());
+/// assert_that!(account, is_valid_iban)
+/// # }
+/// ```
+/// Would fail with a message like ``Expected `account`=Account("FR76") to be a valid IBAN.``
+pub trait Describe {
+    /// The wording of the predicate `method`, or `None` to read its name.
+    fn describe(&self, method: &str) -> Option<String>;
+}
+
+impl<T: Describe + ?Sized> Describe for &T {
+    fn describe(&self, method: &str) -> Option<String> {
+        (**self).describe(method)
+    }
+}
+
+/// A checked value, whose predicates are worded by its `Describe` implementation when it has one,
+/// dispatched as described on `__doubts_check!`.
+pub struct Subject<'a, T: ?Sized>(pub &'a T);
+
+/// The wording of the predicates of the values implementing `Describe`.
+pub trait Described {
+    fn description(&self, method: &str) -> Option<String>;
+}
+
+impl<T: Describe + ?Sized> Described for Subject<'_, T> {
+    fn description(&self, method: &str) -> Option<String> {
+        self.0.describe(method)
+    }
+}
+
+/// The fallback for the values not implementing `Describe`.
+pub trait Undescribed {
+    fn description(&self, _method: &str) -> Option<String> {
+        None
+    }
+}
+
+impl<T: ?Sized> Undescribed for &Subject<'_, T> {}
+
+/// The property checked by a predicate method, like `empty` for `is_empty`, or `power of two` for `is_power_of_two`.
+pub fn property(method_name: &str) -> Option<String> {
+    method_name.strip_prefix("is_").map(|property| property.replace('_', " "))
+}
+
+/// The predicate a method tells, as it follows "to" in a sentence: its first word is conjugated,
+/// like `contain` for `contains`, `start with` for `starts_with` or `have key` for `has_key`.
+pub fn verb(method_name: &str) -> String {
+    let mut words = method_name.split('_').filter(|word| !word.is_empty());
+    let first = match words.next() {
+        Some(first) => first,
+        None => return method_name.to_string(),
+    };
+    let rest: Vec<&str> = words.collect();
+    let verb = match first {
+        "is" | "are" => "be".to_string(),
+        "has" => "have".to_string(),
+        "does" => "do".to_string(),
+        "can" => "be able to".to_string(),
+        // `should_accept` reads `accept`
+        "should" | "must" | "will" if !rest.is_empty() => return rest.join(" "),
+        word => infinitive(word),
+    };
+    if rest.is_empty() { verb } else { format!("{} {}", verb, rest.join(" ")) }
+}
+
+/// The infinitive of a verb in the third person, like `match` for `matches` or `apply` for `applies`.
+fn infinitive(word: &str) -> String {
+    if let Some(stem) = word.strip_suffix("ies").filter(|stem| stem.len() > 1) {
+        format!("{}y", stem)
+    } else if let Some(stem) = ["sses", "shes", "ches", "xes", "zzes", "oes"].iter()
+        .find(|ending| word.ends_with(*ending))
+        .map(|_| &word[..word.len() - 2]) {
+        stem.to_string()
+    } else if word.ends_with("ss") || word.ends_with("us") {
+        word.to_string()
+    } else {
+        word.strip_suffix('s').unwrap_or(word).to_string()
+    }
+}
+
+/// The source of a property path, as given by `stringify!` on its tokens, without the spaces
+/// and line breaks inserted around generic arguments, like `collect::<Vec<_>>()`.
+pub fn path(tokens: &str) -> String {
+    let tokens: Vec<char> = tokens.lines().map(str::trim_start).collect::<Vec<_>>().join(" ").chars().collect();
+    let mut path = String::new();
+    let mut depth = 0;
+    for (i, &c) in tokens.iter().enumerate() {
+        let previous = path.chars().last();
+        let next = tokens.get(i + 1).cloned();
+        match c {
+            '<' if depth > 0 || path.ends_with("::") => depth += 1,
+            '>' if depth > 0 => depth -= 1,
+            ' ' if depth > 0 && (previous == Some('<') || next == Some('<') || next == Some('>')) => continue,
+            ' ' if previous == Some(':') && next == Some('<') => continue,
+            ' ' if previous == Some('>') && next == Some('(') => continue,
+            _ => {}
+        }
+        path.push(c);
+    }
+    path
+}
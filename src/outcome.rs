@@ -0,0 +1,58 @@
+//! Checks on `Option` and `Result` values.
+use std::fmt::Debug;
+
+use crate::render;
+
+/// The `Option` and `Result` values, whose state is told in failure messages.
+pub trait Outcome {
+    /// The state of the value, as it follows "but" in a failure message, like `it is an error: "empty input"`.
+    fn state(&self) -> String;
+}
+
+impl<T: Debug, E: Debug> Outcome for Result<T, E> {
+    fn state(&self) -> String {
+        match self {
+            Ok(value) => format!("it is ok: {}", render::debug(value)),
+            Err(error) => format!("it is an error: {}", render::debug(error)),
+        }
+    }
+}
+
+impl<T: Debug> Outcome for Option<T> {
+    fn state(&self) -> String {
+        match self {
+            Some(value) => format!("it is some: {}", render::debug(value)),
+            None => "it is none".to_string(),
+        }
+    }
+}
+
+impl<O: Outcome + ?Sized> Outcome for &O {
+    fn state(&self) -> String {
+        (**self).state()
+    }
+}
+
+/// A checked value, whose state is told in failure messages when it is an `Option` or a `Result`,
+/// dispatched as described on `__doubts_check!`.
+pub struct Checked<'a, T: ?Sized>(pub &'a T);
+
+/// The state of the `Option` and `Result` values.
+pub trait Stated {
+    fn state(&self) -> Option<String>;
+}
+
+impl<T: Outcome + ?Sized> Stated for Checked<'_, T> {
+    fn state(&self) -> Option<String> {
+        Some(self.0.state())
+    }
+}
+
+/// The fallback for the other values, like the types having their own `is_ok` method.
+pub trait Unstated {
+    fn state(&self) -> Option<String> {
+        None
+    }
+}
+
+impl<T: ?Sized> Unstated for &Checked<'_, T> {}
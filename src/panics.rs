@@ -0,0 +1,90 @@
+//! Checks on the panics of closures.
+use std::any::Any;
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+use crate::Failure;
+
+thread_local! {
+    static SILENCED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// What is expected from the panic of a closure.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Expectation<'a> {
+    Any,
+    /// The message of the panic contains this text.
+    With(&'a str),
+    /// The message of the panic matches this regular expression.
+    #[cfg(feature = "regex")]
+    Matching(&'a str),
+}
+
+/// Checks that `closure` panics as expected.
+pub fn panics<R>(subject: &str, closure: impl FnOnce() -> R, expectation: Expectation) -> Result<(), Box<Failure>> {
+    let failure = match expectation {
+        Expectation::Any => Failure::new(subject, "", "panic"),
+        Expectation::With(text) => Failure::new(subject, "", "panic with").with_expected(format!("{:?}", text)),
+        #[cfg(feature = "regex")]
+        Expectation::Matching(pattern) => Failure::new(subject, "", "panic matching")
+            .with_property("regex")
+            .with_expected(format!("{:?}", pattern)),
+    };
+    let payload = match run(closure) {
+        Ok(_) => return Err(Box::new(failure.with_reason("it did not panic"))),
+        Err(payload) => payload,
+    };
+    let message = match (expectation, message(&*payload)) {
+        (Expectation::Any, _) => return Ok(()),
+        (_, None) => return Err(Box::new(failure.with_reason(panicked(None)))),
+        (_, Some(message)) => message,
+    };
+    let expected = match expectation {
+        Expectation::With(text) => message.contains(text),
+        #[cfg(feature = "regex")]
+        Expectation::Matching(pattern) => match regex::Regex::new(pattern) {
+            Ok(regex) => regex.is_match(message),
+            Err(error) => return Err(Box::new(failure.with_reason("the regex is invalid")
+                .with_detail(format!("  {}", error.to_string().replace('\n', "\n  "))))),
+        },
+        Expectation::Any => true,
+    };
+    if expected { Ok(()) } else { Err(Box::new(failure.with_reason(panicked(Some(message))))) }
+}
+
+/// Checks that `closure` does not panic, giving back its result.
+pub fn returns<R>(subject: &str, closure: impl FnOnce() -> R) -> Result<R, Box<Failure>> {
+    run(closure).map_err(|payload| Box::new(Failure::new(subject, "", "panic").negated()
+        .with_reason(panicked(message(&*payload)))))
+}
+
+/// Runs `closure`, catching its panic without printing it.
+fn run<R>(closure: impl FnOnce() -> R) -> Result<R, Box<dyn Any + Send>> {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !SILENCED.with(Cell::get) {
+                previous(info)
+            }
+        }));
+    });
+    let silenced = SILENCED.with(|silenced| silenced.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(closure));
+    SILENCED.with(|flag| flag.set(silenced));
+    result
+}
+
+/// The message of a panic, when it has one.
+fn message(payload: &(dyn Any + Send)) -> Option<&str> {
+    payload.downcast_ref::<String>().map(String::as_str)
+        .or_else(|| payload.downcast_ref::<&str>().copied())
+}
+
+fn panicked(message: Option<&str>) -> String {
+    match message {
+        Some(message) => format!("it panicked with {:?}", message),
+        None => "it panicked with a payload that is not a message".to_string(),
+    }
+}
@@ -0,0 +1,54 @@
+//! Rendering of the values in failure messages.
+use std::fmt::{Debug, Display};
+
+/// The `Debug` representation of a value, pretty-printed with the `pretty` feature.
+pub fn debug<T: Debug + ?Sized>(value: &T) -> String {
+    if cfg!(feature = "pretty") {
+        format!("{:#?}", value)
+    } else {
+        format!("{:?}", value)
+    }
+}
+
+/// An expected or actual value of a property, or an argument of a method, printed with `Display` when it has it,
+/// and with `Debug` otherwise, dispatched as described on `__doubts_check!`.
+/// With the `pretty` feature, `Debug` is preferred, to be pretty-printed and compared line by line.
+pub struct Shown<'a, T: ?Sized>(pub &'a T);
+
+/// The `Display` representation of the values having one.
+pub trait Displayed {
+    fn rendering(&self) -> String;
+}
+
+/// The `Debug` representation of the values having one.
+pub trait Debugged {
+    fn rendering(&self) -> String;
+}
+
+#[cfg(not(feature = "pretty"))]
+impl<T: Display + ?Sized> Displayed for Shown<'_, T> {
+    fn rendering(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+#[cfg(not(feature = "pretty"))]
+impl<T: Debug + ?Sized> Debugged for &Shown<'_, T> {
+    fn rendering(&self) -> String {
+        debug(self.0)
+    }
+}
+
+#[cfg(feature = "pretty")]
+impl<T: Debug + ?Sized> Debugged for Shown<'_, T> {
+    fn rendering(&self) -> String {
+        debug(self.0)
+    }
+}
+
+#[cfg(feature = "pretty")]
+impl<T: Display + ?Sized> Displayed for &Shown<'_, T> {
+    fn rendering(&self) -> String {
+        self.0.to_string()
+    }
+}
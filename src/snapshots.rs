@@ -0,0 +1,146 @@
+//! Snapshots of the `Debug` rendering of values, stored next to the tests checking them.
+//!
+//! The snapshot `name` checked in `tests/api.rs` is stored in `tests/snapshots/api__name.snap`.
+//! The file holds the `{:#?}` rendering of the value, followed by a line break, and nothing else:
+//! this format is stable, so that snapshots can be written, read and reviewed as plain text.
+//!
+//! On the first run, the snapshot is written and the check passes. On the next runs, the rendering
+//! is compared with it: when they differ, the check fails with a line diff, and the new rendering is
+//! written to `api__name.snap.new` beside the snapshot, in the same format, to be reviewed with `cargo doubts`.
+//! When the `DOUBTS_UPDATE` environment variable is set to `1`, differing snapshots are rewritten instead.
+use std::fmt::Debug;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::diff;
+use crate::render;
+use crate::Failure;
+
+/// The extension of the pending snapshots, written when a check fails.
+pub const PENDING: &str = "snap.new";
+
+/// Checks that the rendering of `actual` matches the snapshot `name` of the test file `file`,
+/// as given by `file!()` in the crate whose manifest is in `manifest_dir`.
+pub fn check<T: Debug + ?Sized>(subject: &str, actual: &T, manifest_dir: &str, file: &str, name: &str) -> Result<(), Box<Failure>> {
+    compare(subject, actual, manifest_dir, file, name, updating())
+}
+
+/// Checks the snapshot, rewriting it when it differs and `update` is set.
+fn compare<T: Debug + ?Sized>(subject: &str, actual: &T, manifest_dir: &str, file: &str, name: &str, update: bool) -> Result<(), Box<Failure>> {
+    let rendering = format!("{:#?}\n", actual);
+    let snapshot = path(Path::new(manifest_dir), Path::new(file), name);
+    let pending = snapshot.with_extension(PENDING);
+    let failure = Failure::new(subject, render::debug(actual), "match")
+        .with_property("snapshot")
+        .with_expected(format!("{:?}", name));
+    let written = match fs::read_to_string(&snapshot) {
+        Ok(ref expected) if *expected == rendering => remove(&pending),
+        Ok(ref expected) if !update => {
+            return Err(Box::new(match fs::write(&pending, &rendering) {
+                Ok(()) => failure.with_reason(format!("it differs from {}", snapshot.display())),
+                Err(error) => failure.with_reason(format!("it differs from {}, and {} cannot be written: {}", snapshot.display(), pending.display(), error)),
+            }.with_detail(format!("Diff (- snapshot / + actual):\n{}", if cfg!(feature = "pretty") {
+                diff::colored(expected, &rendering)
+            } else {
+                diff::plain(expected, &rendering)
+            }.trim_end()))));
+        }
+        Ok(_) => write(&snapshot, &rendering).and_then(|()| remove(&pending)),
+        Err(ref error) if error.kind() == io::ErrorKind::NotFound => write(&snapshot, &rendering),
+        Err(error) => Err(error),
+    };
+    written.map_err(|error| Box::new(failure.with_reason(format!("{} cannot be used: {}", snapshot.display(), error))))
+}
+
+/// Whether the differing snapshots should be rewritten, as asked with `DOUBTS_UPDATE=1`.
+fn updating() -> bool {
+    std::env::var_os("DOUBTS_UPDATE").is_some_and(|update| update == "1")
+}
+
+/// The path of the snapshot `name` of the test file `file`.
+///
+/// `file!()` is relative to the workspace root, which may be an ancestor of the manifest directory.
+fn path(manifest_dir: &Path, file: &Path, name: &str) -> PathBuf {
+    let file = manifest_dir.ancestors()
+        .map(|root| root.join(file))
+        .find(|path| path.is_file())
+        .unwrap_or_else(|| manifest_dir.join(file));
+    let stem = file.file_stem().map_or_else(String::new, |stem| stem.to_string_lossy().into_owned());
+    file.with_file_name("snapshots").join(format!("{}__{}.snap", stem, name))
+}
+
+fn write(snapshot: &Path, rendering: &str) -> io::Result<()> {
+    if let Some(directory) = snapshot.parent() {
+        fs::create_dir_all(directory)?;
+    }
+    fs::write(snapshot, rendering)
+}
+
+fn remove(pending: &Path) -> io::Result<()> {
+    match fs::remove_file(pending) {
+        Err(ref error) if error.kind() == io::ErrorKind::NotFound => Ok(()),
+        removed => removed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::temporary::Directory;
+
+    /// A crate with the test file `tests/api.rs`.
+    struct Crate(Directory);
+
+    impl Crate {
+        fn new(name: &str) -> Crate {
+            Crate(Directory::new(name, &["tests/api.rs"]))
+        }
+
+        fn root(&self) -> &Path {
+            &self.0 .0
+        }
+
+        fn check<T: Debug>(&self, actual: &T, update: bool) -> Result<(), Box<Failure>> {
+            compare("actual", actual, self.root().to_str().unwrap(), "tests/api.rs", "name", update)
+        }
+
+        fn snapshot(&self) -> PathBuf {
+            self.root().join("tests/snapshots/api__name.snap")
+        }
+    }
+
+    #[test]
+    fn path_is_beside_the_test_file() {
+        let root = Crate::new("path");
+        assert_eq!(path(root.root(), Path::new("tests/api.rs"), "name"), root.snapshot());
+        assert_eq!(path(&root.root().join("tests"), Path::new("tests/api.rs"), "name"), root.snapshot());
+    }
+
+    #[test]
+    fn check_writes_compares_and_updates_snapshots() {
+        let root = Crate::new("check");
+        let pending = root.snapshot().with_extension(PENDING);
+
+        // the first check writes the snapshot, the next ones compare with it
+        assert!(root.check(&vec![1, 2], false).is_ok());
+        assert_eq!(fs::read_to_string(root.snapshot()).unwrap(), "[\n    1,\n    2,\n]\n");
+        assert!(root.check(&vec![1, 2], false).is_ok());
+        assert!(!pending.exists());
+
+        // a differing rendering is written beside the snapshot, which is kept
+        let failure = root.check(&vec![1, 3], false).unwrap_err();
+        assert!(failure.to_string().contains(&format!("it differs from {}", root.snapshot().display())));
+        assert_eq!(fs::read_to_string(&pending).unwrap(), "[\n    1,\n    3,\n]\n");
+        assert_eq!(fs::read_to_string(root.snapshot()).unwrap(), "[\n    1,\n    2,\n]\n");
+
+        // the pending snapshot is removed once the check passes again
+        assert!(root.check(&vec![1, 2], false).is_ok());
+        assert!(!pending.exists());
+
+        // when updating, a differing snapshot is rewritten
+        assert!(root.check(&vec![1, 3], true).is_ok());
+        assert_eq!(fs::read_to_string(root.snapshot()).unwrap(), "[\n    1,\n    3,\n]\n");
+        assert!(!pending.exists());
+    }
+}
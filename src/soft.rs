@@ -0,0 +1,80 @@
+//! Collection of the failures recorded by `check_that!` inside `assert_all!` blocks.
+use std::cell::RefCell;
+
+thread_local! {
+    static SCOPES: RefCell<Vec<Vec<String>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// An open `assert_all!` block. Dropping it closes the block, even when its body panics.
+pub struct Scope {
+    depth: usize,
+}
+
+impl Scope {
+    pub fn enter() -> Scope {
+        SCOPES.with(|scopes| {
+            let mut scopes = scopes.borrow_mut();
+            scopes.push(Vec::new());
+            Scope { depth: scopes.len() }
+        })
+    }
+
+    /// Closes the block. If any failure was recorded in it, they are reported together
+    /// to the enclosing block, or in a panic when there is none.
+    pub fn finish(self) {
+        let failures = SCOPES.with(|scopes| {
+            std::mem::take(&mut scopes.borrow_mut()[self.depth - 1])
+        });
+        drop(self);
+        if !failures.is_empty() {
+            record(report(&failures))
+        }
+    }
+}
+
+impl Drop for Scope {
+    /// When the body panics, the failures recorded so far are not lost: they are given to the enclosing block,
+    /// or printed when there is none, since panicking again would abort.
+    fn drop(&mut self) {
+        let failures = SCOPES.with(|scopes| {
+            let mut scopes = scopes.borrow_mut();
+            let failures: Vec<String> = scopes.drain(self.depth - 1..).flatten().collect();
+            match scopes.last_mut() {
+                Some(parent) if std::thread::panicking() && !failures.is_empty() => {
+                    parent.push(report(&failures));
+                    Vec::new()
+                }
+                _ => failures,
+            }
+        });
+        if std::thread::panicking() && !failures.is_empty() {
+            eprintln!("{}", report(&failures));
+        }
+    }
+}
+
+/// Records a failure in the innermost open block, or panics with it if there is none.
+pub fn record(message: String) {
+    let unrecorded = SCOPES.with(|scopes| match scopes.borrow_mut().last_mut() {
+        Some(failures) => {
+            failures.push(message);
+            None
+        }
+        None => Some(message),
+    });
+    if let Some(message) = unrecorded {
+        panic!("{}", message)
+    }
+}
+
+fn report(failures: &[String]) -> String {
+    let mut report = format!(
+        "{} expectation{} failed:",
+        failures.len(),
+        if failures.len() > 1 { "s" } else { "" }
+    );
+    for (i, failure) in failures.iter().enumerate() {
+        report.push_str(&format!("\n{}. {}", i + 1, failure.replace('\n', "\n   ")));
+    }
+    report
+}
@@ -0,0 +1,150 @@
+//! Checks on strings, whose messages point at where the strings diverge.
+use std::fmt::Debug;
+
+use crate::render;
+use crate::Failure;
+
+/// How the tested string should relate to the expected one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Relation {
+    Prefix,
+    Suffix,
+    Substring,
+    IgnoringCase,
+}
+
+/// Where two strings diverge, and how to draw it under their `Debug` representations.
+struct Divergence {
+    reason: String,
+    actual_shift: usize,
+    expected_shift: usize,
+    caret: usize,
+}
+
+/// Checks that `actual` relates to `expected` as asked, or not when `negated`.
+pub fn check<S, E>(relation: Relation, subject: &str, actual: &S, expected: &E, negated: bool) -> Result<(), Box<Failure>>
+    where S: AsRef<str> + Debug + ?Sized, E: AsRef<str> + Debug + ?Sized {
+    let (text, pattern) = (actual.as_ref(), expected.as_ref());
+    let failure = match relation {
+        Relation::Prefix => Failure::new(subject, render::debug(actual), "start with"),
+        Relation::Suffix => Failure::new(subject, render::debug(actual), "end with"),
+        Relation::Substring => Failure::new(subject, render::debug(actual), "contain").with_property("substring"),
+        Relation::IgnoringCase => Failure::new(subject, render::debug(actual), "equal ignoring case"),
+    }.with_expected(format!("{:?}", pattern));
+    let divergence = match relation {
+        Relation::Prefix => prefix(text, pattern),
+        Relation::Suffix => suffix(text, pattern),
+        Relation::Substring => substring(text, pattern),
+        Relation::IgnoringCase => ignoring_case(text, pattern),
+    };
+    match (negated, divergence) {
+        (false, Ok(())) | (true, Err(_)) => Ok(()),
+        (true, Ok(())) => Err(Box::new(failure.negated())),
+        (false, Err(None)) => Err(Box::new(failure)),
+        (false, Err(Some(divergence))) => Err(Box::new(failure
+            .with_reason(divergence.reason)
+            .with_detail(format!("  {}{:?}\n  {}{:?}\n  {}^",
+                " ".repeat(divergence.actual_shift), text,
+                " ".repeat(divergence.expected_shift), pattern,
+                " ".repeat(divergence.caret))))),
+    }
+}
+
+/// Checks that `actual` matches the regular expression `pattern`, or not when `negated`.
+#[cfg(feature = "regex")]
+pub fn regex<S, P>(subject: &str, actual: &S, pattern: &P, negated: bool) -> Result<(), Box<Failure>>
+    where S: AsRef<str> + Debug + ?Sized, P: AsRef<str> + ?Sized {
+    let failure = Failure::new(subject, render::debug(actual), "match")
+        .with_property("regex")
+        .with_expected(format!("{:?}", pattern.as_ref()));
+    match regex::Regex::new(pattern.as_ref()) {
+        Err(error) => Err(Box::new(failure.with_reason("the regex is invalid").with_detail(format!("  {}", error.to_string().replace('\n', "\n  "))))),
+        Ok(regex) if regex.is_match(actual.as_ref()) == negated => Err(Box::new(if negated { failure.negated() } else { failure })),
+        Ok(_) => Ok(()),
+    }
+}
+
+fn prefix(text: &str, pattern: &str) -> Result<(), Option<Divergence>> {
+    let common = text.chars().zip(pattern.chars()).take_while(|(a, b)| a == b).count();
+    if common == pattern.chars().count() {
+        return Ok(());
+    }
+    Err(Some(Divergence {
+        reason: differs(text, common),
+        actual_shift: 0,
+        expected_shift: 0,
+        caret: column(pattern, common),
+    }))
+}
+
+fn suffix(text: &str, pattern: &str) -> Result<(), Option<Divergence>> {
+    let common = text.chars().rev().zip(pattern.chars().rev()).take_while(|(a, b)| a == b).count();
+    let (length, expected) = (text.chars().count(), pattern.chars().count());
+    if common == expected {
+        return Ok(());
+    }
+    // the strings are aligned on their last characters
+    let (actual_width, expected_width) = (width(text), width(pattern));
+    let expected_shift = actual_width.saturating_sub(expected_width);
+    Err(Some(Divergence {
+        reason: if common == length { differs(text, length) } else { differs(text, length - common - 1) },
+        actual_shift: expected_width.saturating_sub(actual_width),
+        expected_shift,
+        caret: expected_shift + column(pattern, expected - common - 1),
+    }))
+}
+
+fn substring(text: &str, pattern: &str) -> Result<(), Option<Divergence>> {
+    if text.contains(pattern) {
+        return Ok(());
+    }
+    // the closest match is the longest run of the pattern found in the text
+    let chars: Vec<char> = text.chars().collect();
+    let (start, common) = (0..chars.len())
+        .map(|start| (start, chars[start..].iter().zip(pattern.chars()).take_while(|(a, b)| **a == *b).count()))
+        .fold((0, 0), |best, candidate| if candidate.1 > best.1 { candidate } else { best });
+    if common == 0 {
+        return Err(None);
+    }
+    let expected_shift = column(text, start) - 1;
+    Err(Some(Divergence {
+        reason: format!("the closest match differs at char {}", start + common),
+        actual_shift: 0,
+        expected_shift,
+        caret: expected_shift + column(pattern, common),
+    }))
+}
+
+fn ignoring_case(text: &str, pattern: &str) -> Result<(), Option<Divergence>> {
+    let common = text.chars().zip(pattern.chars()).take_while(|(a, b)| a.to_lowercase().eq(b.to_lowercase())).count();
+    let (length, expected) = (text.chars().count(), pattern.chars().count());
+    if common == length && common == expected {
+        return Ok(());
+    }
+    Err(Some(Divergence {
+        reason: differs(text, common),
+        actual_shift: 0,
+        expected_shift: 0,
+        caret: if common == expected { column(text, common) } else { column(pattern, common) },
+    }))
+}
+
+/// Tells at which char `text` differs, or that it is too short.
+fn differs(text: &str, at: usize) -> String {
+    match text.chars().count() {
+        length if length > at => format!("it differs at char {}", at),
+        1 => "it has only 1 char".to_string(),
+        length => format!("it has only {} chars", length),
+    }
+}
+
+/// The width of the `Debug` representation of `text`, without the quotes.
+fn width(text: &str) -> usize {
+    format!("{:?}", text).chars().count() - 2
+}
+
+/// The column of the char at `index` in the `Debug` representation of `text`.
+fn column(text: &str, index: usize) -> usize {
+    let end = text.char_indices().nth(index).map_or(text.len(), |(i, _)| i);
+    1 + width(&text[..end])
+}
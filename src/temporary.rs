@@ -0,0 +1,27 @@
+//! A temporary directory for the tests writing files, shared by the tests of the library and of `cargo-doubts`.
+use std::fs;
+use std::path::PathBuf;
+
+/// A directory of files, unique to the test process, removed at the end of the test.
+pub struct Directory(pub PathBuf);
+
+impl Directory {
+    /// Creates the directory `name` with the files at the relative paths `files`, each holding its own path.
+    pub fn new(name: &str, files: &[&str]) -> Directory {
+        let root = std::env::temp_dir().join(format!("doubts-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        for file in files {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, *file).unwrap();
+        }
+        Directory(root)
+    }
+}
+
+impl Drop for Directory {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
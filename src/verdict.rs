@@ -0,0 +1,16 @@
+//! Results of the predicate methods, and of the checks named like them.
+use crate::Failure;
+
+/// What a predicate method returned: a `bool`, or one of the checks standing for `is_sorted`, `is_sorted_by_key`
+/// and `has_key` on the values without such a method, which explain their failures.
+pub trait Verdict<A: ?Sized> {
+    /// Whether the expectation, negated or not, is met by `actual`, given the subject and the arguments of the method as written.
+    /// `Err(None)` is a plain `bool` telling it is not, whose failure is worded by the caller.
+    fn verdict(self, subject: &str, actual: &A, arguments: &str, negated: bool) -> Result<(), Option<Box<Failure>>>;
+}
+
+impl<A: ?Sized> Verdict<A> for bool {
+    fn verdict(self, _subject: &str, _actual: &A, _arguments: &str, negated: bool) -> Result<(), Option<Box<Failure>>> {
+        if self != negated { Ok(()) } else { Err(None) }
+    }
+}